@@ -63,6 +63,8 @@ impl Child for usize {
 // https://www.cs.usfca.edu/~galles/visualization/AVLtree.html
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::utils::serialize::AVLTreeData<T>", bound(deserialize = "T: serde::Deserialize<'de> + PartialOrd")))]
 pub struct AVLTree<T> {
     pub(crate) root: Vec<Option<T>>,
     pub(crate) balance_factor: Vec<u32>, // "height" of node*
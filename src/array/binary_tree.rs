@@ -9,6 +9,8 @@ use crate::utils::util::{
     Counting, Utility, OrderedTraversal, BFS, Insert, InsertAt, Util, Contains, Remove
 };
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::utils::serialize::BinaryTreeData<T>", bound(deserialize = "T: serde::Deserialize<'de> + PartialOrd")))]
 pub struct BinaryTree<T> {
     pub(crate) root: Vec<Option<T>>,
     pub(crate) nodes: u32,
@@ -1,46 +1,82 @@
 use std::borrow::Borrow;
 use std::fmt::{Debug, Display, Formatter};
 use std::{fmt, fs};
-use std::collections::VecDeque;
+use std::collections::{HashMap, TryReserveError, VecDeque};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::str::FromStr;
+use crate::array::graph::node_id::{next_graph_tag, NodeId};
+use crate::array::graph::label::NoLabel;
+use crate::array::graph::weight::Weight;
+use crate::utils::bit_matrix::BitMatrix;
+use crate::utils::read_file::{read_graph, GraphParseError};
+
+pub mod shortest_path;
+pub mod spanning_tree;
+pub mod ordering;
+pub mod components;
+pub mod connectivity;
+pub mod flow;
+pub mod node_id;
+pub mod csr;
+pub mod weight;
+pub mod label;
+pub mod traversal;
 
 /*
 Child
  */
-#[derive(Debug, Copy)]
-pub struct Child {
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Child<W = i64, E = NoLabel> {
     pub(crate) idx: usize,
-    pub(crate) weight: u32,
+    pub(crate) weight: W,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) label: Option<E>
 }
 
-impl Clone for Child {
-    fn clone(&self) -> Self {
-        *self
+impl<W, E> Child<W, E>
+    where W: Weight {
+    // Unweighted graphs still store a weight, it is always zero
+    pub(crate) fn new(idx: usize) -> Child<W, E> {
+        Child {
+            idx,
+            weight: W::zero(),
+            label: None
+        }
     }
-}
 
-impl Child {
-    pub(crate) fn new(idx: usize) -> Child {
+    pub(crate) fn new_with_weight(idx: usize, weight: W) -> Child<W, E> {
         Child {
             idx,
-            weight: 0
+            weight,
+            label: None
         }
     }
 
-    pub(crate) fn new_with_weight(idx: usize, weight: u32) -> Child {
+    pub(crate) fn new_with_label(idx: usize, weight: W, label: E) -> Child<W, E> {
         Child {
             idx,
             weight,
+            label: Some(label)
         }
     }
 
-    pub(crate) fn get_clean_value(&self, weighted: bool) -> String {
+    pub(crate) fn get_clean_value(&self, weighted: bool) -> String
+        where E: Display {
         if weighted {
-            format!("{} {}", self.idx, self.weight)
+            format!("{} {}", self.labeled_idx(), self.weight)
         } else {
-            format!("{}", self.idx)
+            self.labeled_idx()
+        }
+    }
+
+    // The index with the label after it, the same way it is written in files
+    pub(crate) fn labeled_idx(&self) -> String
+        where E: Display {
+        match &self.label {
+            Some(label) => format!("{}:{label}", self.idx),
+            None => self.idx.to_string()
         }
     }
 }
@@ -49,38 +85,40 @@ impl Child {
 Node
  */
 #[derive(Debug)]
-pub struct Node<T> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node<T, W = i64, E = NoLabel> {
     pub(crate) val: T,
-    pub(crate) children: Vec<Child> // child and weight
+    pub(crate) children: Vec<Child<W, E>> // child and weight
 }
 
-impl<T> Node<T> {
-    pub(crate) fn new(val: T) -> Node<T> {
+impl<T, W, E> Node<T, W, E> {
+    pub(crate) fn new(val: T) -> Node<T, W, E> {
         Node {
             val,
             children: vec![]
         }
     }
 
-    pub(crate) fn new_with_children(val: T, children: Vec<Child>) -> Node<T> {
+    pub(crate) fn new_with_children(val: T, children: Vec<Child<W, E>>) -> Node<T, W, E> {
         Node {
             val,
             children
         }
     }
 
-    pub(crate) fn add_child(&mut self, node: Child) {
+    pub(crate) fn add_child(&mut self, node: Child<W, E>) {
         self.children.push(node);
     }
 
-    pub(crate) fn add_children(&mut self, nodes: Vec<Child>) {
+    pub(crate) fn add_children(&mut self, nodes: Vec<Child<W, E>>) {
         for node in nodes {
             self.add_child(node);
         }
     }
 }
 
-impl<T> From<(T, Vec<usize>)> for Node<T> {
+impl<T, W, E> From<(T, Vec<usize>)> for Node<T, W, E>
+    where W: Weight {
     fn from(value: (T, Vec<usize>)) -> Self {
         Node {
             val: value.0,
@@ -92,8 +130,9 @@ impl<T> From<(T, Vec<usize>)> for Node<T> {
     }
 }
 
-impl<T> From<(T, Vec<(usize, u32)>)> for Node<T> {
-    fn from(value: (T, Vec<(usize, u32)>)) -> Self {
+impl<T, W, E> From<(T, Vec<(usize, W)>)> for Node<T, W, E>
+    where W: Weight {
+    fn from(value: (T, Vec<(usize, W)>)) -> Self {
         Node {
             val: value.0,
             children: value.1
@@ -108,27 +147,53 @@ impl<T> From<(T, Vec<(usize, u32)>)> for Node<T> {
 Graph
  */
 #[derive(Debug)]
-pub struct Graph<T> {
-    pub(crate) nodes: Vec<Node<T>>,
-    pub(crate) weighted: bool
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "crate::utils::serialize::GraphData<T, W, E>", bound(deserialize = "T: serde::Deserialize<'de>, W: serde::Deserialize<'de> + Weight, E: serde::Deserialize<'de> + PartialEq")))]
+pub struct Graph<T, W = i64, E = NoLabel> {
+    pub(crate) nodes: Vec<Node<T, W, E>>,
+    pub(crate) weighted: bool,
+    pub(crate) directed: bool,  // If false every edge is stored in both nodes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) tag: u64,        // Which graph a NodeId belongs to
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) generation: u32  // Increased every time nodes are removed
 }
 
 /*
 New
  */
-impl<T> Graph<T> {
-    pub(crate) fn new() -> Graph<T> {
+impl<T, W, E> Graph<T, W, E> {
+    pub(crate) fn new() -> Graph<T, W, E> {
         Graph {
             nodes: vec![],
-            weighted: false
+            weighted: false,
+            directed: true,
+            tag: next_graph_tag(),
+            generation: 0
         }
     }
 
-    pub(crate) fn add_node(&mut self, node: Node<T>) {
+    pub(crate) fn new_undirected() -> Graph<T, W, E> {
+        Graph {
+            nodes: vec![],
+            weighted: false,
+            directed: false,
+            tag: next_graph_tag(),
+            generation: 0
+        }
+    }
+
+    pub(crate) fn add_node(&mut self, node: Node<T, W, E>) -> NodeId {
         self.nodes.push(node);
+
+        NodeId {
+            idx: self.nodes.len() - 1,
+            graph: self.tag,
+            generation: self.generation
+        }
     }
 
-    pub(crate) fn add_nodes(&mut self, nodes: Vec<Node<T>>) {
+    pub(crate) fn add_nodes(&mut self, nodes: Vec<Node<T, W, E>>) {
         for node in nodes {
             self.add_node(node);
         }
@@ -139,122 +204,225 @@ impl<T> Graph<T> {
     }
 }
 
-impl<T> Graph<T>
-    where T: Default {
-    pub(crate) fn new_with_size(size: usize) -> Graph<T> {
-        let mut v = vec![];
+/*
+Edges
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    pub(crate) fn add_edge(&mut self, from: usize, to: usize) {
+        self.add_edge_with_weight(from, to, W::zero());
+    }
 
-        v.resize_with(size, || Node {
-            val: T::default(),
-            children: vec![]
-        });
+    // In undirected mode the edge is added to both nodes
+    pub(crate) fn add_edge_with_weight(&mut self, from: usize, to: usize, weight: W) {
+        self.nodes[from].add_child(Child::new_with_weight(to, weight));
 
-        Graph {
-            nodes: v,
-            weighted: false
+        if !self.directed && from != to {
+            self.nodes[to].add_child(Child::new_with_weight(from, weight));
         }
     }
 }
 
-/*
-From implementations
- */
-impl<T> From<Vec<(T, Vec<usize>)>> for Graph<T>
-    where T: Default + Clone {
-    fn from(value: Vec<(T, Vec<usize>)>) -> Self {
-        let mut graph = Graph::<T>::new_with_size(value.len());
+// Weight and label of the edges going up and the edges going down between two nodes
+type EdgeEnds<'a, W, E> = (Vec<(W, Option<&'a E>)>, Vec<(W, Option<&'a E>)>);
+
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight, E: PartialEq {
+    // Edges (from, to) that do not have a matching edge (to, from) with the same weight and label
+    pub(crate) fn asymmetric_edges(&self) -> Vec<(usize, usize)> {
+        // (lowest index, highest index) -> the edges between them
+        let mut pairs: HashMap<(usize, usize), EdgeEnds<'_, W, E>> = HashMap::new();
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            for c in node.children.iter().filter(|c| c.idx != idx) {
+                let weight = if self.weighted { c.weight } else { W::zero() };
+                let (up, down) = pairs.entry((idx.min(c.idx), idx.max(c.idx))).or_default();
+
+                if idx < c.idx {
+                    up.push((weight, c.label.as_ref()));
+                } else {
+                    down.push((weight, c.label.as_ref()));
+                }
+            }
+        }
 
-        for (idx, node) in value.iter().enumerate() {
-            graph.nodes[idx] = Node::from(node.clone())
+        let mut edges: Vec<(usize, usize)> = vec![];
+
+        for ((low, high), (mut up, mut down)) in pairs {
+            // Every edge going up cancels out one edge going down with the same weight and label
+            up.retain(|edge| match down.iter().position(|e| e == edge) {
+                Some(pos) => {
+                    down.swap_remove(pos);
+                    false
+                }
+                None => true
+            });
+
+            if !up.is_empty() {
+                edges.push((low, high));
+            }
+
+            if !down.is_empty() {
+                edges.push((high, low));
+            }
         }
 
-        graph
+        edges.sort();
+        edges.dedup();
+
+        edges
     }
-}
 
-impl From<File> for Graph<String> {
-    fn from(value: File) -> Self {
-        let mut graph: Graph<String> = Graph::new();
+    pub(crate) fn validate_undirected(&self) -> Result<(), Vec<(usize, usize)>> {
+        let edges = self.asymmetric_edges();
 
-        let mut reader = BufReader::new(value);
+        if edges.is_empty() {
+            Ok(())
+        } else {
+            Err(edges)
+        }
+    }
+}
 
-        // read the first line
-        let mut number_of_nodes = String::new();
-        reader.read_line(&mut number_of_nodes).unwrap();
+/*
+Removal
 
-        let number_of_nodes: u32 = number_of_nodes.trim_end().parse().unwrap();
+Removing nodes moves the nodes after it down and makes every NodeId stale. The returned mapping gives the new index of every old index,
+or None if the node was removed.
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight, E: PartialEq {
+    // Removes the first edge from -> to, in undirected mode the edge back with the same weight and label is removed as well.
+    // No nodes are moved, so there is no mapping, the removed edge is returned instead
+    pub(crate) fn remove_edge(&mut self, from: usize, to: usize) -> Option<Child<W, E>> {
+        let pos = self.nodes[from].children.iter().position(|c| c.idx == to)?;
+        let removed = self.nodes[from].children.remove(pos);
+
+        if !self.directed && from != to {
+            let back = self.nodes[to].children.iter()
+                .position(|c| c.idx == from && c.weight == removed.weight && c.label == removed.label);
+
+            if let Some(pos) = back {
+                self.nodes[to].children.remove(pos);
+            }
+        }
 
-        reader.lines().for_each(|line| {
-            let line = line.unwrap();
+        Some(removed)
+    }
+}
 
-            let mut split = line.split_whitespace();
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
 
-            let idx: u32 = split.next().unwrap().parse().unwrap();
-            let value: String = split.next().unwrap().parse().unwrap();
+    // Removes the node and every edge to it
+    pub(crate) fn remove_node(&mut self, idx: usize) -> Vec<Option<usize>> {
+        assert!(idx < self.nodes.len(), "Node {idx} does not exist, the graph has {} nodes", self.nodes.len());
 
-            let num_nodes: u32 = split.next().unwrap().parse().unwrap();
+        let keep: Vec<bool> = (0..self.nodes.len()).map(|i| i != idx).collect();
 
-            let node_children: Vec<usize> = (0..num_nodes)
-                .map(|i| split.next().unwrap().parse().unwrap())
-                .collect();
+        self.compact(&keep)
+    }
 
-            graph.add_node(Node::from((value, node_children)));
-        });
+    // Keeps the nodes where pred is true, in the same order
+    pub(crate) fn retain_nodes(&mut self, mut pred: impl FnMut(&Node<T, W, E>) -> bool) -> Vec<Option<usize>> {
+        let keep: Vec<bool> = self.nodes.iter().map(&mut pred).collect();
 
-        graph
+        self.compact(&keep)
     }
-}
 
+    fn compact(&mut self, keep: &[bool]) -> Vec<Option<usize>> {
+        let mut mapping: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut next = 0;
 
-impl From<File> for Graph<char> {
-    fn from(value: File) -> Self {
-        let mut graph: Graph<char> = Graph::new();
+        for (old, kept) in keep.iter().enumerate() {
+            if *kept {
+                mapping[old] = Some(next);
+                next += 1;
+            }
+        }
 
-        let mut reader = BufReader::new(value);
+        // Nothing moves if every node is kept, so the ids are still good
+        if next == self.nodes.len() {
+            return mapping;
+        }
 
-        // read the first line
-        let mut number_of_nodes = String::new();
-        reader.read_line(&mut number_of_nodes).unwrap();
+        let mut old = 0;
 
-        let number_of_nodes: u32 = number_of_nodes.trim_end().parse().unwrap();
+        self.generation += 1;
+        self.nodes.retain(|_| {
+            old += 1;
+            keep[old - 1]
+        });
 
-        reader.lines().for_each(|line| {
-            let line = line.unwrap();
+        // Drop the edges to removed nodes and point the rest at the new indexes
+        for node in self.nodes.iter_mut() {
+            node.children.retain_mut(|c| match mapping[c.idx] {
+                Some(new) => {
+                    c.idx = new;
+                    true
+                }
+                None => false
+            });
+        }
 
-            let mut split = line.split_whitespace();
+        mapping
+    }
+}
 
-            let idx: u32 = split.next().unwrap().parse().unwrap();
-            let value: char = split.next().unwrap().parse().unwrap();
+impl<T, W, E> Graph<T, W, E>
+    where T: Default {
+    pub(crate) fn new_with_size(size: usize) -> Graph<T, W, E> {
+        Graph::try_new_with_size(size).unwrap_or_else(|err| panic!("Could not make room for {size} nodes: {err}"))
+    }
 
-            let num_nodes: usize = split.next().unwrap().parse().unwrap();
-            let children_count = split.clone().count();
+    // Gives an error instead of aborting when there is not enough memory for the nodes
+    pub(crate) fn try_new_with_size(size: usize) -> Result<Graph<T, W, E>, TryReserveError> {
+        let mut v = vec![];
 
-            let weighted = children_count != num_nodes;
+        v.try_reserve_exact(size)?;
+        v.resize_with(size, || Node {
+            val: T::default(),
+            children: vec![]
+        });
 
-            if weighted {
-                let node_children: Vec<(usize, u32)> = (0..num_nodes)
-                    .map(|i| (
-                        split.next().unwrap().parse().unwrap(),
-                        split.next().unwrap().parse().unwrap()
-                    ))
-                    .collect();
+        Ok(Graph {
+            nodes: v,
+            weighted: false,
+            directed: true,
+            tag: next_graph_tag(),
+            generation: 0
+        })
+    }
+}
 
-                graph.add_node(Node::from((value, node_children)));
-            } else {
-                let node_children: Vec<usize> = (0..num_nodes)
-                    .map(|i| split.next().unwrap().parse().unwrap())
-                    .collect();
+/*
+From implementations
+ */
+impl<T> From<Vec<(T, Vec<usize>)>> for Graph<T>
+    where T: Default + Clone {
+    fn from(value: Vec<(T, Vec<usize>)>) -> Self {
+        let mut graph = Graph::<T>::new_with_size(value.len());
 
-                graph.add_node(Node::from((value, node_children)));
-            }
-        });
+        for (idx, node) in value.iter().enumerate() {
+            graph.nodes[idx] = Node::from(node.clone())
+        }
 
         graph
     }
 }
 
-impl<T> Display for Graph<T>
-    where T: Display {
+// Weights are found by looking at the number of values after the child count
+impl<T, W, E> TryFrom<File> for Graph<T, W, E>
+    where T: FromStr + Default, W: Weight, E: FromStr {
+    type Error = GraphParseError;
+
+    fn try_from(value: File) -> Result<Self, Self::Error> {
+        read_graph(BufReader::new(value), None)
+    }
+}
+
+impl<T, W, E> Display for Graph<T, W, E>
+    where T: Display, W: Weight, E: Display {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let mut display_string = String::new();
 
@@ -263,6 +431,10 @@ impl<T> Display for Graph<T>
 
         let index_width = display_string.len();
 
+        if !self.directed {
+            display_string += " undirected";
+        }
+
         let mut num_child_width = 0;
         let mut child_width = 0;
         let mut val_width = 0;
@@ -323,93 +495,33 @@ impl<T> Display for Graph<T>
 /*
 Traversal
  */
-impl<T> Graph<T> {
-    pub(crate) fn dfs(&self, from_index: usize) -> Vec<&Node<T>> {
-        let mut parent: VecDeque<usize> = VecDeque::new();
-        let mut out: Vec<&Node<T>> = vec![&self.nodes[from_index]];
-        let mut visited: Vec<bool> = vec![false; self.nodes.len()];
-
-        parent.reserve(self.nodes.len());
-        out.reserve(self.nodes.len());
-        visited[from_index] = true;
-
-        let mut current_node = from_index;
-
-        'outer: loop {
-            // loop through all children to the current node
-            for c in self.nodes[current_node].children.iter() {
-                // check if not the child is already visited
-                if !visited[c.idx] {
-                    // register as visited
-                    visited[c.idx] = true;
-
-                    // add parent to parent stack
-                    parent.push_back(current_node);
-
-                    // add child node as current node
-                    current_node = c.idx;
-
-                    // add child node to output
-                    out.push(&self.nodes[current_node]);
-
-                    // continue loop
-                    continue 'outer;
-                }
-            }
-
-            // there are no childs, move back to parent, or break out of loop
-            match parent.pop_back() {
-                Some(v) => current_node = v,
-                None => return out
-            }
-        }
+impl<T, W, E> Graph<T, W, E> {
+    pub(crate) fn dfs(&self, from_index: usize) -> Vec<&Node<T, W, E>> {
+        self.dfs_iter(from_index).map(|(_, node)| node).collect()
     }
 
-    pub(crate) fn bfs(&self, from_index: usize) -> Vec<&Node<T>> {
-        let mut childs: VecDeque<usize> = VecDeque::new();
-        let mut out: Vec<&Node<T>> = vec![];
-        let mut visited: Vec<bool> = vec![false; self.nodes.len()];
-
-        childs.reserve(self.nodes.len());
-        out.reserve(self.nodes.len());
-
-        let mut current_node: usize = from_index;
-
-        loop {
-            if !visited[current_node] {
-                self.nodes[current_node].children.iter()
-                    .filter(|c| !visited[c.idx])
-                    .for_each(|c| childs.push_front(c.idx));
-
-                visited[current_node] = true;
-                out.push(&self.nodes[current_node]);
-            }
-
-            match childs.pop_back() {
-                Some(v) => current_node = v,
-                None => return out
-            }
-        }
+    pub(crate) fn bfs(&self, from_index: usize) -> Vec<&Node<T, W, E>> {
+        self.bfs_iter(from_index).map(|(_, node)| node).collect()
     }
 }
 
 /*
 Get neighborhood
  */
-impl<T> Graph<T> {
+impl<T, W, E> Graph<T, W, E> {
     pub(crate) fn get_neighborhood_matrix(&self) -> Vec<Vec<bool>> {
-        let mut neighborhood = vec![vec![false; self.nodes.len()]; self.nodes.len()];
+        self.neighborhood_bit_matrix().to_vec()
+    }
 
-        for i in 0..neighborhood.len() {
-            neighborhood[i][i] = true;
-        }
+    pub(crate) fn neighborhood_bit_matrix(&self) -> BitMatrix {
+        let mut neighborhood = BitMatrix::identity(self.nodes.len());
 
         self.nodes
             .iter()
             .enumerate()
             .for_each(|(idx, node)| node.children
                     .iter()
-                    .for_each(|child| neighborhood[idx][child.idx] = true)
+                    .for_each(|child| neighborhood.set(idx, child.idx, true))
             );
 
         neighborhood
@@ -419,16 +531,21 @@ impl<T> Graph<T> {
 /*
 Reachability
  */
-impl<T> Graph<T> {
-    pub(crate) fn warshall(self) -> Vec<Vec<bool>> {
+impl<T, W, E> Graph<T, W, E> {
+    pub(crate) fn warshall(&self) -> Vec<Vec<bool>> {
+        self.warshall_bit_matrix().to_vec()
+    }
+
+    // If i can reach k, i can reach everything k can reach, so the whole row of k is added to i
+    pub(crate) fn warshall_bit_matrix(&self) -> BitMatrix {
         let neighborhood_size = self.nodes.len();
-        let mut neighborhood = self.get_neighborhood_matrix();
+        let mut neighborhood = self.neighborhood_bit_matrix();
 
         (0..neighborhood_size).for_each(|k| {
             (0..neighborhood_size).for_each(|i| {
-                (0..neighborhood_size).for_each(|j| {
-                    neighborhood[i][j] = neighborhood[i][j] || (neighborhood[i][k] && neighborhood[k][j]);
-                });
+                if neighborhood.get(i, k) {
+                    neighborhood.or_row(i, k);
+                }
             });
         });
 
@@ -0,0 +1,152 @@
+use crate::array::graph::{Child, Graph, Node};
+use crate::array::graph::weight::Weight;
+use crate::utils::union_find::UnionFind;
+
+/*
+Strongly connected components
+ */
+impl<T, W, E> Graph<T, W, E> {
+    // Tarjan's algorithm. Returns the component id of every node, the ids are in topological order
+    // so edges between components always go from a lower to a higher id.
+    pub(crate) fn strongly_connected_components(&self) -> Vec<usize> {
+        let num_nodes = self.nodes.len();
+
+        let mut index: Vec<Option<usize>> = vec![None; num_nodes];  // Order the node was found in
+        let mut low_link: Vec<usize> = vec![0; num_nodes];
+        let mut on_stack: Vec<bool> = vec![false; num_nodes];
+        let mut stack: Vec<usize> = vec![];
+        let mut component: Vec<usize> = vec![0; num_nodes];
+
+        let mut next_index = 0;
+        let mut num_components = 0;
+
+        for from_index in 0..num_nodes {
+            if index[from_index].is_some() {
+                continue;
+            }
+
+            // (node, position of the next child to look at)
+            let mut path: Vec<(usize, usize)> = vec![(from_index, 0)];
+
+            index[from_index] = Some(next_index);
+            low_link[from_index] = next_index;
+            next_index += 1;
+            stack.push(from_index);
+            on_stack[from_index] = true;
+
+            while let Some((current_node, child_pos)) = path.pop() {
+                if let Some(c) = self.nodes[current_node].children.get(child_pos) {
+                    path.push((current_node, child_pos + 1));
+
+                    match index[c.idx] {
+                        None => {
+                            index[c.idx] = Some(next_index);
+                            low_link[c.idx] = next_index;
+                            next_index += 1;
+                            stack.push(c.idx);
+                            on_stack[c.idx] = true;
+
+                            path.push((c.idx, 0));
+                        }
+                        Some(child_index) if on_stack[c.idx] => {
+                            low_link[current_node] = low_link[current_node].min(child_index);
+                        }
+                        _ => {}
+                    }
+
+                    continue;
+                }
+
+                // All children are done, the node is the root of a component if it can not reach further up
+                if Some(low_link[current_node]) == index[current_node] {
+                    loop {
+                        let node = stack.pop().unwrap();
+                        on_stack[node] = false;
+                        component[node] = num_components;
+
+                        if node == current_node {
+                            break;
+                        }
+                    }
+
+                    num_components += 1;
+                }
+
+                if let Some((parent, _)) = path.last() {
+                    low_link[*parent] = low_link[*parent].min(low_link[current_node]);
+                }
+            }
+        }
+
+        // Tarjan finds the components in reverse topological order
+        component.iter()
+            .map(|id| num_components - 1 - id)
+            .collect()
+    }
+
+}
+
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    // One node per strongly connected component holding the indexes of the nodes in it.
+    // Only the cheapest edge between two components is kept.
+    pub(crate) fn condensation(&self) -> Graph<Vec<usize>, W> {
+        let component = self.strongly_connected_components();
+        let num_components = component.iter().map(|id| id + 1).max().unwrap_or(0);
+
+        let mut graph: Graph<Vec<usize>, W> = Graph::new_with_size(num_components);
+        graph.weighted = self.weighted;
+
+        for (idx, id) in component.iter().enumerate() {
+            graph.nodes[*id].val.push(idx);
+        }
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            for c in node.children.iter() {
+                let (from, to) = (component[idx], component[c.idx]);
+
+                if from == to {
+                    continue;
+                }
+
+                let from_node: &mut Node<Vec<usize>, W> = &mut graph.nodes[from];
+
+                match from_node.children.iter_mut().find(|child| child.idx == to) {
+                    Some(child) if c.weight < child.weight => child.weight = c.weight,
+                    Some(_) => {}
+                    None => from_node.add_child(Child::new_with_weight(to, c.weight))
+                }
+            }
+        }
+
+        graph
+    }
+}
+
+/*
+Weakly connected components
+
+The components when the direction of the edges is ignored.
+ */
+impl<T, W, E> Graph<T, W, E> {
+    // Component id of every node, the ids are numbered by the lowest node in each component
+    pub(crate) fn weakly_connected_components(&self) -> Vec<usize> {
+        self.union_find().set_ids()
+    }
+
+    pub(crate) fn component_count(&self) -> usize {
+        self.union_find().num_sets()
+    }
+
+    fn union_find(&self) -> UnionFind {
+        let mut sets = UnionFind::new(self.nodes.len());
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            node.children.iter().for_each(|c| {
+                sets.union(idx, c.idx);
+            });
+        }
+
+        sets
+    }
+}
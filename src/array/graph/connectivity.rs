@@ -0,0 +1,133 @@
+use crate::array::graph::Graph;
+
+/*
+Articulation points and bridges
+
+Directed graphs are treated as undirected, an edge and the edge back counts as one edge.
+ */
+impl<T, W, E> Graph<T, W, E> {
+    // Nodes that disconnects the graph if removed
+    pub(crate) fn articulation_points(&self) -> Vec<usize> {
+        let (articulation_points, _) = self.low_link_dfs();
+
+        articulation_points
+    }
+
+    // Edges that disconnects the graph if removed, as (lowest index, highest index)
+    pub(crate) fn bridges(&self) -> Vec<(usize, usize)> {
+        let (_, bridges) = self.low_link_dfs();
+
+        bridges
+    }
+
+    fn undirected_adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency: Vec<Vec<usize>> = self.nodes.iter()
+            .enumerate()
+            .map(|(idx, node)| node.children.iter()
+                .map(|c| c.idx)
+                .filter(|child| *child != idx)
+                .collect())
+            .collect();
+
+        if self.directed {
+            for (idx, node) in self.nodes.iter().enumerate() {
+                node.children.iter()
+                    .filter(|c| c.idx != idx)
+                    .for_each(|c| adjacency[c.idx].push(idx));
+            }
+
+            adjacency.iter_mut().for_each(|children| {
+                children.sort();
+                children.dedup();
+            });
+        }
+
+        adjacency
+    }
+
+    fn low_link_dfs(&self) -> (Vec<usize>, Vec<(usize, usize)>) {
+        let adjacency = self.undirected_adjacency();
+        let num_nodes = self.nodes.len();
+
+        let mut discovered: Vec<Option<usize>> = vec![None; num_nodes];
+        let mut low_link: Vec<usize> = vec![0; num_nodes];
+        let mut is_articulation: Vec<bool> = vec![false; num_nodes];
+        let mut bridges: Vec<(usize, usize)> = vec![];
+
+        let mut time = 0;
+
+        for root in 0..num_nodes {
+            if discovered[root].is_some() {
+                continue;
+            }
+
+            discovered[root] = Some(time);
+            low_link[root] = time;
+            time += 1;
+
+            let mut root_children = 0;
+
+            // (node, parent, position of the next child, edge to the parent is skipped)
+            let mut path: Vec<(usize, Option<usize>, usize, bool)> = vec![(root, None, 0, false)];
+
+            while let Some((current_node, parent, child_pos, skipped_parent)) = path.pop() {
+                if let Some(child) = adjacency[current_node].get(child_pos) {
+                    let child = *child;
+
+                    // Skip the edge back to the parent once, a parallel edge is a real cycle
+                    if Some(child) == parent && !skipped_parent {
+                        path.push((current_node, parent, child_pos + 1, true));
+                        continue;
+                    }
+
+                    path.push((current_node, parent, child_pos + 1, skipped_parent));
+
+                    match discovered[child] {
+                        None => {
+                            discovered[child] = Some(time);
+                            low_link[child] = time;
+                            time += 1;
+
+                            if current_node == root {
+                                root_children += 1;
+                            }
+
+                            path.push((child, Some(current_node), 0, false));
+                        }
+                        Some(child_time) => {
+                            low_link[current_node] = low_link[current_node].min(child_time);
+                        }
+                    }
+
+                    continue;
+                }
+
+                // All children are done, update the parent
+                if let Some(parent) = parent {
+                    low_link[parent] = low_link[parent].min(low_link[current_node]);
+
+                    let parent_time = discovered[parent].unwrap();
+
+                    if low_link[current_node] >= parent_time && parent != root {
+                        is_articulation[parent] = true;
+                    }
+
+                    if low_link[current_node] > parent_time {
+                        bridges.push((parent.min(current_node), parent.max(current_node)));
+                    }
+                }
+            }
+
+            // The root is only an articulation point if the dfs had to start more than one subtree from it
+            is_articulation[root] = root_children > 1;
+        }
+
+        bridges.sort();
+
+        let articulation_points = (0..num_nodes)
+            .filter(|idx| is_articulation[*idx])
+            .collect();
+
+        (articulation_points, bridges)
+    }
+}
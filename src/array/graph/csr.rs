@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+use crate::array::graph::Graph;
+
+/*
+Compressed sparse row
+
+Read only copy of a graph where all the edges are in flat arrays.
+The children of node i are targets[offsets[i]..offsets[i + 1]], with the weights at the same positions.
+ */
+#[derive(Debug)]
+pub struct CsrGraph<T, W = i64> {
+    pub(crate) values: Vec<T>,
+    pub(crate) offsets: Vec<usize>,
+    pub(crate) targets: Vec<usize>,
+    pub(crate) weights: Vec<W>,
+    pub(crate) weighted: bool,
+    pub(crate) directed: bool
+}
+
+impl<T, W, E> From<Graph<T, W, E>> for CsrGraph<T, W> {
+    fn from(value: Graph<T, W, E>) -> Self {
+        let num_edges = value.nodes.iter().map(|node| node.children.len()).sum();
+
+        let mut csr = CsrGraph {
+            values: Vec::with_capacity(value.nodes.len()),
+            offsets: Vec::with_capacity(value.nodes.len() + 1),
+            targets: Vec::with_capacity(num_edges),
+            weights: Vec::with_capacity(num_edges),
+            weighted: value.weighted,
+            directed: value.directed
+        };
+
+        csr.offsets.push(0);
+
+        for node in value.nodes {
+            for c in node.children {
+                csr.targets.push(c.idx);
+                csr.weights.push(c.weight);
+            }
+
+            csr.offsets.push(csr.targets.len());
+            csr.values.push(node.val);
+        }
+
+        csr
+    }
+}
+
+impl<T, W, E> From<&Graph<T, W, E>> for CsrGraph<T, W>
+    where T: Clone, W: Copy {
+    fn from(value: &Graph<T, W, E>) -> Self {
+        let values: Vec<T> = value.nodes.iter().map(|node| node.val.clone()).collect();
+        let mut offsets: Vec<usize> = Vec::with_capacity(value.nodes.len() + 1);
+
+        offsets.push(0);
+
+        for node in value.nodes.iter() {
+            offsets.push(offsets[offsets.len() - 1] + node.children.len());
+        }
+
+        let children = value.nodes.iter().flat_map(|node| node.children.iter());
+
+        CsrGraph {
+            values,
+            offsets,
+            targets: children.clone().map(|c| c.idx).collect(),
+            weights: children.map(|c| c.weight).collect(),
+            weighted: value.weighted,
+            directed: value.directed
+        }
+    }
+}
+
+/*
+Neighborhood
+ */
+impl<T, W> CsrGraph<T, W> {
+    pub(crate) fn num_nodes(&self) -> usize {
+        self.values.len()
+    }
+
+    pub(crate) fn num_edges(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub(crate) fn val(&self, idx: usize) -> &T {
+        &self.values[idx]
+    }
+
+    pub(crate) fn children(&self, idx: usize) -> &[usize] {
+        &self.targets[self.offsets[idx]..self.offsets[idx + 1]]
+    }
+
+    pub(crate) fn weights(&self, idx: usize) -> &[W] {
+        &self.weights[self.offsets[idx]..self.offsets[idx + 1]]
+    }
+
+    pub(crate) fn has_edge(&self, from: usize, to: usize) -> bool {
+        self.children(from).contains(&to)
+    }
+
+    pub(crate) fn get_neighborhood_matrix(&self) -> Vec<Vec<bool>> {
+        let mut neighborhood = vec![vec![false; self.num_nodes()]; self.num_nodes()];
+
+        for (idx, row) in neighborhood.iter_mut().enumerate() {
+            row[idx] = true;
+            self.children(idx).iter().for_each(|child| row[*child] = true);
+        }
+
+        neighborhood
+    }
+}
+
+/*
+Traversal
+
+Visits the nodes in the same order as Graph::dfs and Graph::bfs.
+ */
+impl<T, W> CsrGraph<T, W> {
+    pub(crate) fn dfs(&self, from_index: usize) -> Vec<&T> {
+        let mut out: Vec<&T> = Vec::with_capacity(self.num_nodes());
+        let mut visited: Vec<bool> = vec![false; self.num_nodes()];
+
+        // (node, position in targets of the next child to look at)
+        let mut path: Vec<(usize, usize)> = vec![(from_index, self.offsets[from_index])];
+
+        visited[from_index] = true;
+        out.push(&self.values[from_index]);
+
+        while let Some((current_node, pos)) = path.last_mut() {
+            let end = self.offsets[*current_node + 1];
+
+            // Children before pos are already visited, so there is no need to look at them again
+            match (*pos..end).find(|edge| !visited[self.targets[*edge]]) {
+                Some(edge) => {
+                    let child = self.targets[edge];
+                    *pos = edge + 1;
+
+                    visited[child] = true;
+                    out.push(&self.values[child]);
+                    path.push((child, self.offsets[child]));
+                }
+                None => {
+                    path.pop();
+                }
+            }
+        }
+
+        out
+    }
+
+    pub(crate) fn bfs(&self, from_index: usize) -> Vec<&T> {
+        let mut out: Vec<&T> = Vec::with_capacity(self.num_nodes());
+        let mut visited: Vec<bool> = vec![false; self.num_nodes()];
+        let mut queue: VecDeque<usize> = VecDeque::with_capacity(self.num_nodes());
+
+        visited[from_index] = true;
+        queue.push_back(from_index);
+
+        while let Some(current_node) = queue.pop_front() {
+            out.push(&self.values[current_node]);
+
+            for child in self.children(current_node) {
+                if !visited[*child] {
+                    visited[*child] = true;
+                    queue.push_back(*child);
+                }
+            }
+        }
+
+        out
+    }
+}
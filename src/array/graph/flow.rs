@@ -0,0 +1,135 @@
+use std::collections::VecDeque;
+use crate::array::graph::Graph;
+use crate::array::graph::weight::Weight;
+
+/*
+Max flow
+ */
+#[derive(Debug)]
+pub struct MaxFlow<W = i64> {
+    pub(crate) value: W,
+    pub(crate) flow: Vec<Vec<W>>,           // flow[i][c] is the flow on the c-th child edge of node i
+    pub(crate) source_side: Vec<bool>,      // Nodes on the source side of the minimum cut
+    pub(crate) cut: Vec<(usize, usize)>     // Edges going from the source side to the sink side
+}
+
+// Edge in the residual network, every edge has a paired edge going back at index ^ 1.
+// The capacity left on the edge going back is the flow on the edge, so nothing goes below zero for unsigned weights
+struct ResidualEdge<W> {
+    to: usize,
+    remaining: W
+}
+
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    // Edmonds-Karp, the weight of an edge is the capacity. Negative weights count as no capacity.
+    pub(crate) fn max_flow(&self, source: usize, sink: usize) -> MaxFlow<W> {
+        let mut edges: Vec<ResidualEdge<W>> = vec![];
+        let mut adjacency: Vec<Vec<usize>> = vec![vec![]; self.nodes.len()];
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            for c in node.children.iter() {
+                adjacency[idx].push(edges.len());
+                let capacity = if self.edge_cost(c) > W::zero() { self.edge_cost(c) } else { W::zero() };
+                edges.push(ResidualEdge { to: c.idx, remaining: capacity });
+
+                adjacency[c.idx].push(edges.len());
+                edges.push(ResidualEdge { to: idx, remaining: W::zero() });
+            }
+        }
+
+        let mut value = W::zero();
+
+        // Keep adding flow along the shortest path with capacity left
+        loop {
+            let previous_edge = residual_bfs(&edges, &adjacency, source);
+
+            if source == sink || previous_edge[sink].is_none() {
+                break;
+            }
+
+            // Find the bottleneck of the path
+            let mut bottleneck: Option<W> = None;
+            let mut current_node = sink;
+
+            while let Some(edge) = previous_edge[current_node] {
+                let remaining = edges[edge].remaining;
+
+                if bottleneck.is_none_or(|b| remaining < b) {
+                    bottleneck = Some(remaining);
+                }
+
+                current_node = edges[edge ^ 1].to;
+            }
+
+            // The path has at least one edge, since the source is not the sink
+            let bottleneck = bottleneck.unwrap();
+
+            let mut current_node = sink;
+
+            while let Some(edge) = previous_edge[current_node] {
+                edges[edge].remaining = edges[edge].remaining - bottleneck;
+                edges[edge ^ 1].remaining = edges[edge ^ 1].remaining + bottleneck;
+                current_node = edges[edge ^ 1].to;
+            }
+
+            value = value.saturating_add(bottleneck);
+        }
+
+        // The nodes still reachable in the residual network is the source side of the minimum cut
+        let reachable = residual_bfs(&edges, &adjacency, source);
+        let source_side: Vec<bool> = (0..self.nodes.len())
+            .map(|idx| idx == source || reachable[idx].is_some())
+            .collect();
+
+        let mut flow: Vec<Vec<W>> = vec![];
+        let mut cut: Vec<(usize, usize)> = vec![];
+        let mut edge = 0;
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let mut node_flow: Vec<W> = vec![];
+
+            for c in node.children.iter() {
+                node_flow.push(edges[edge + 1].remaining);
+                edge += 2;
+
+                if source_side[idx] && !source_side[c.idx] {
+                    cut.push((idx, c.idx));
+                }
+            }
+
+            flow.push(node_flow);
+        }
+
+        MaxFlow {
+            value,
+            flow,
+            source_side,
+            cut
+        }
+    }
+}
+
+// Returns the edge used to reach every node, only following edges with capacity left
+fn residual_bfs<W: Weight>(edges: &[ResidualEdge<W>], adjacency: &[Vec<usize>], source: usize) -> Vec<Option<usize>> {
+    let mut previous_edge: Vec<Option<usize>> = vec![None; adjacency.len()];
+    let mut visited: Vec<bool> = vec![false; adjacency.len()];
+    let mut queue: VecDeque<usize> = VecDeque::new();
+
+    visited[source] = true;
+    queue.push_back(source);
+
+    while let Some(current_node) = queue.pop_front() {
+        for edge in adjacency[current_node].iter() {
+            let to = edges[*edge].to;
+
+            if !visited[to] && edges[*edge].remaining > W::zero() {
+                visited[to] = true;
+                previous_edge[to] = Some(*edge);
+                queue.push_back(to);
+            }
+        }
+    }
+
+    previous_edge
+}
@@ -0,0 +1,63 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::array::graph::{Child, Graph};
+use crate::array::graph::weight::Weight;
+
+/*
+Edge labels and parallel edges
+
+Every edge can carry a label, e.g. "road" or "rail". Adding an edge never replaces an edge that is already there,
+so there can be any number of parallel edges between two nodes, with different weights and labels.
+In files the label is written after the child index, "3:road".
+ */
+
+// Label type of graphs without labels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoLabel;
+
+impl FromStr for NoLabel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Err(format!("the graph does not have labels, found ({s})"))
+    }
+}
+
+impl Display for NoLabel {
+    fn fmt(&self, _: &mut Formatter<'_>) -> fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight, E: Clone {
+    // In undirected mode the edge back gets the same label
+    pub(crate) fn add_labeled_edge(&mut self, from: usize, to: usize, weight: W, label: E) {
+        self.nodes[from].add_child(Child::new_with_label(to, weight, label.clone()));
+
+        if !self.directed && from != to {
+            self.nodes[to].add_child(Child::new_with_label(from, weight, label));
+        }
+    }
+}
+
+impl<T, W, E> Graph<T, W, E> {
+    // Every edge from -> to, parallel edges included, in the order they were added
+    pub(crate) fn edges_between(&self, from: usize, to: usize) -> Vec<&Child<W, E>> {
+        self.nodes[from].children.iter()
+            .filter(|c| c.idx == to)
+            .collect()
+    }
+
+    // Every edge with the label as (from, edge). Undirected graphs gives both directions of every edge
+    pub(crate) fn edges_with_label(&self, label: &E) -> Vec<(usize, &Child<W, E>)>
+        where E: PartialEq {
+        self.nodes.iter()
+            .enumerate()
+            .flat_map(|(idx, node)| node.children.iter().map(move |c| (idx, c)))
+            .filter(|(_, c)| c.label.as_ref() == Some(label))
+            .collect()
+    }
+}
@@ -0,0 +1,370 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::ops;
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::array::graph::{Child, Graph, Node};
+use crate::array::graph::flow::MaxFlow;
+use crate::array::graph::shortest_path::{AStarPath, ShortestPathError, ShortestPaths};
+use crate::array::graph::ordering::Cycle;
+use crate::array::graph::traversal::Forest;
+use crate::array::graph::weight::Weight;
+
+static NEXT_GRAPH: AtomicU64 = AtomicU64::new(0);
+
+// Every graph gets its own tag, so ids from one graph can not be used on another
+pub(crate) fn next_graph_tag() -> u64 {
+    NEXT_GRAPH.fetch_add(1, Ordering::Relaxed)
+}
+
+/*
+NodeId
+
+Handle to a node in one graph. Removing nodes moves the indexes, so it also increases the generation of the graph,
+and every id made before that stops working.
+ */
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    pub(crate) idx: usize,
+    pub(crate) graph: u64,
+    pub(crate) generation: u32
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NodeIdError {
+    Foreign,                                    // The id is from another graph
+    Stale { generation: u32, current: u32 },    // Nodes has been removed since the id was made
+    OutOfRange { idx: usize, num_nodes: usize }
+}
+
+impl Display for NodeIdError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeIdError::Foreign => write!(f, "the node id is from another graph"),
+            NodeIdError::Stale { generation, current } => {
+                write!(f, "the node id is from generation {generation}, but the graph is at generation {current}")
+            }
+            NodeIdError::OutOfRange { idx, num_nodes } => write!(f, "node {idx} is out of range, the graph has {num_nodes} nodes")
+        }
+    }
+}
+
+impl std::error::Error for NodeIdError {}
+
+// What an id has to match to be used on a graph: the graph, its generation and the number of nodes.
+// Results of the id versions keep the scope of the graph they came from, so they can give ids back
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct IdScope {
+    graph: u64,
+    generation: u32,
+    num_nodes: usize
+}
+
+impl IdScope {
+    pub(crate) fn id(&self, idx: usize) -> NodeId {
+        NodeId { idx, graph: self.graph, generation: self.generation }
+    }
+
+    pub(crate) fn ids<'a>(&self, indexes: impl IntoIterator<Item = &'a usize>) -> Vec<NodeId> {
+        indexes.into_iter().map(|idx| self.id(*idx)).collect()
+    }
+
+    pub(crate) fn resolve(&self, id: NodeId) -> Result<usize, NodeIdError> {
+        if id.graph != self.graph {
+            return Err(NodeIdError::Foreign);
+        }
+
+        if id.generation != self.generation {
+            return Err(NodeIdError::Stale { generation: id.generation, current: self.generation });
+        }
+
+        if id.idx >= self.num_nodes {
+            return Err(NodeIdError::OutOfRange { idx: id.idx, num_nodes: self.num_nodes });
+        }
+
+        Ok(id.idx)
+    }
+}
+
+impl<T, W, E> Graph<T, W, E> {
+    pub(crate) fn id_scope(&self) -> IdScope {
+        IdScope { graph: self.tag, generation: self.generation, num_nodes: self.nodes.len() }
+    }
+
+    pub(crate) fn node_id(&self, idx: usize) -> Option<NodeId> {
+        if idx >= self.nodes.len() {
+            return None;
+        }
+
+        Some(self.id_scope().id(idx))
+    }
+
+    pub(crate) fn ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len()).map(|idx| self.id_scope().id(idx))
+    }
+
+    // The index of the node, if the id is still valid for this graph
+    pub(crate) fn resolve(&self, id: NodeId) -> Result<usize, NodeIdError> {
+        self.id_scope().resolve(id)
+    }
+
+    pub(crate) fn get(&self, id: NodeId) -> Result<&Node<T, W, E>, NodeIdError> {
+        let idx = self.resolve(id)?;
+
+        Ok(&self.nodes[idx])
+    }
+
+    pub(crate) fn get_mut(&mut self, id: NodeId) -> Result<&mut Node<T, W, E>, NodeIdError> {
+        let idx = self.resolve(id)?;
+
+        Ok(&mut self.nodes[idx])
+    }
+}
+
+/*
+Id versions
+
+The same as the methods that take an index, but the ids are checked first, so a stale or foreign id is an error
+instead of silently using whatever node is at that index now. Nodes in the results are ids as well: the result is
+wrapped in ById, which turns the indexes in to ids of the graph the result came from, and checks the ids it is asked about.
+The result with the indexes is still there as result.
+ */
+#[derive(Debug, PartialEq)]
+pub struct ById<R> {
+    pub(crate) result: R,
+    pub(crate) scope: IdScope
+}
+
+impl ById<Forest> {
+    pub(crate) fn trees(&self) -> Vec<Vec<NodeId>> {
+        self.result.trees.iter().map(|tree| self.scope.ids(tree)).collect()
+    }
+
+    pub(crate) fn num_trees(&self) -> usize {
+        self.result.num_trees()
+    }
+
+    pub(crate) fn depth(&self, id: NodeId) -> Result<Option<usize>, NodeIdError> {
+        Ok(self.result.depth[self.scope.resolve(id)?])
+    }
+
+    pub(crate) fn tree_of(&self, id: NodeId) -> Result<Option<usize>, NodeIdError> {
+        Ok(self.result.tree_of(self.scope.resolve(id)?))
+    }
+}
+
+impl<W> ById<ShortestPaths<W>>
+    where W: Weight {
+    pub(crate) fn distance_to(&self, id: NodeId) -> Result<Option<W>, NodeIdError> {
+        Ok(self.result.distance_to(self.scope.resolve(id)?))
+    }
+
+    pub(crate) fn path_to(&self, id: NodeId) -> Result<Option<Vec<NodeId>>, NodeIdError> {
+        Ok(self.result.path_to(self.scope.resolve(id)?).map(|path| self.scope.ids(&path)))
+    }
+
+    pub(crate) fn unreachable(&self) -> Vec<NodeId> {
+        self.scope.ids(&self.result.unreachable())
+    }
+}
+
+impl<W> ById<AStarPath<W>>
+    where W: Weight {
+    pub(crate) fn path(&self) -> Vec<NodeId> {
+        self.scope.ids(&self.result.path)
+    }
+
+    pub(crate) fn cost(&self) -> W {
+        self.result.cost
+    }
+}
+
+impl<W> ById<MaxFlow<W>>
+    where W: Weight {
+    pub(crate) fn value(&self) -> W {
+        self.result.value
+    }
+
+    pub(crate) fn cut(&self) -> Vec<(NodeId, NodeId)> {
+        self.result.cut.iter().map(|(from, to)| (self.scope.id(*from), self.scope.id(*to))).collect()
+    }
+
+    pub(crate) fn is_source_side(&self, id: NodeId) -> Result<bool, NodeIdError> {
+        Ok(self.result.source_side[self.scope.resolve(id)?])
+    }
+}
+
+impl ById<Cycle> {
+    pub(crate) fn nodes(&self) -> Vec<NodeId> {
+        self.scope.ids(&self.result.0)
+    }
+}
+
+// Removing nodes makes every id stale, this gives the new id for an id from before the removal
+#[derive(Debug)]
+pub struct IdMapping {
+    pub(crate) mapping: Vec<Option<usize>>,
+    pub(crate) old: IdScope,
+    pub(crate) new: IdScope
+}
+
+impl IdMapping {
+    // None if the node was removed
+    pub(crate) fn get(&self, old_id: NodeId) -> Result<Option<NodeId>, NodeIdError> {
+        Ok(self.mapping[self.old.resolve(old_id)?].map(|idx| self.new.id(idx)))
+    }
+}
+
+impl<T, W, E> Graph<T, W, E> {
+    fn by_id<R>(&self, result: R) -> ById<R> {
+        ById { result, scope: self.id_scope() }
+    }
+
+    pub(crate) fn dfs_by_id(&self, from: NodeId) -> Result<Vec<&Node<T, W, E>>, NodeIdError> {
+        Ok(self.dfs(self.resolve(from)?))
+    }
+
+    pub(crate) fn bfs_by_id(&self, from: NodeId) -> Result<Vec<&Node<T, W, E>>, NodeIdError> {
+        Ok(self.bfs(self.resolve(from)?))
+    }
+
+    pub(crate) fn dfs_iter_by_id(&self, from: NodeId) -> Result<impl Iterator<Item = (NodeId, &Node<T, W, E>)>, NodeIdError> {
+        let scope = self.id_scope();
+
+        Ok(self.dfs_iter(self.resolve(from)?).map(move |(idx, node)| (scope.id(idx), node)))
+    }
+
+    pub(crate) fn bfs_iter_by_id(&self, from: NodeId) -> Result<impl Iterator<Item = (NodeId, &Node<T, W, E>)>, NodeIdError> {
+        let scope = self.id_scope();
+
+        Ok(self.bfs_iter(self.resolve(from)?).map(move |(idx, node)| (scope.id(idx), node)))
+    }
+
+    pub(crate) fn bfs_from_many_by_id(&self, sources: &[NodeId]) -> Result<ById<Forest>, NodeIdError> {
+        let sources = sources.iter()
+            .map(|id| self.resolve(*id))
+            .collect::<Result<Vec<usize>, NodeIdError>>()?;
+
+        Ok(self.by_id(self.bfs_from_many(&sources)))
+    }
+
+    pub(crate) fn dfs_forest_by_id(&self) -> ById<Forest> {
+        self.by_id(self.dfs_forest())
+    }
+
+    pub(crate) fn bfs_forest_by_id(&self) -> ById<Forest> {
+        self.by_id(self.bfs_forest())
+    }
+
+    pub(crate) fn edges_between_by_id(&self, from: NodeId, to: NodeId) -> Result<Vec<&Child<W, E>>, NodeIdError> {
+        Ok(self.edges_between(self.resolve(from)?, self.resolve(to)?))
+    }
+
+    pub(crate) fn topological_sort_by_id(&self) -> Result<Vec<NodeId>, ById<Cycle>> {
+        match self.topological_sort() {
+            Ok(order) => Ok(self.id_scope().ids(&order)),
+            Err(cycle) => Err(self.by_id(cycle))
+        }
+    }
+
+    // The nodes of every component, components are in the same order as the component ids
+    pub(crate) fn strongly_connected_components_by_id(&self) -> Vec<Vec<NodeId>> {
+        let scope = self.id_scope();
+        let component = self.strongly_connected_components();
+        let mut components: Vec<Vec<NodeId>> = vec![vec![]; component.iter().max().map_or(0, |c| c + 1)];
+
+        for (idx, c) in component.into_iter().enumerate() {
+            components[c].push(scope.id(idx));
+        }
+
+        components
+    }
+
+    pub(crate) fn articulation_points_by_id(&self) -> Vec<NodeId> {
+        self.id_scope().ids(&self.articulation_points())
+    }
+
+    pub(crate) fn bridges_by_id(&self) -> Vec<(NodeId, NodeId)> {
+        let scope = self.id_scope();
+
+        self.bridges().into_iter().map(|(from, to)| (scope.id(from), scope.id(to))).collect()
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    pub(crate) fn add_edge_between(&mut self, from: NodeId, to: NodeId, weight: W) -> Result<(), NodeIdError> {
+        let from = self.resolve(from)?;
+        let to = self.resolve(to)?;
+
+        self.add_edge_with_weight(from, to, weight);
+
+        Ok(())
+    }
+
+    pub(crate) fn add_labeled_edge_by_id(&mut self, from: NodeId, to: NodeId, weight: W, label: E) -> Result<(), NodeIdError>
+        where E: Clone {
+        let from = self.resolve(from)?;
+        let to = self.resolve(to)?;
+
+        self.add_labeled_edge(from, to, weight, label);
+
+        Ok(())
+    }
+
+    pub(crate) fn remove_edge_by_id(&mut self, from: NodeId, to: NodeId) -> Result<Option<Child<W, E>>, NodeIdError>
+        where E: PartialEq {
+        let from = self.resolve(from)?;
+        let to = self.resolve(to)?;
+
+        Ok(self.remove_edge(from, to))
+    }
+
+    // Every id made before this, the given one included, is stale afterwards, the mapping gives the new ids
+    pub(crate) fn remove_node_by_id(&mut self, id: NodeId) -> Result<IdMapping, NodeIdError> {
+        let old = self.id_scope();
+        let mapping = self.remove_node(self.resolve(id)?);
+
+        Ok(IdMapping { mapping, old, new: self.id_scope() })
+    }
+
+    pub(crate) fn retain_nodes_by_id(&mut self, pred: impl FnMut(&Node<T, W, E>) -> bool) -> IdMapping {
+        let old = self.id_scope();
+        let mapping = self.retain_nodes(pred);
+
+        IdMapping { mapping, old, new: self.id_scope() }
+    }
+
+    pub(crate) fn dijkstra_by_id(&self, from: NodeId) -> Result<ById<ShortestPaths<W>>, ShortestPathError<W>> {
+        Ok(self.by_id(self.dijkstra(self.resolve(from)?)?))
+    }
+
+    pub(crate) fn bellman_ford_by_id(&self, from: NodeId) -> Result<ById<ShortestPaths<W>>, ShortestPathError<W>> {
+        Ok(self.by_id(self.bellman_ford(self.resolve(from)?)?))
+    }
+
+    pub(crate) fn astar_by_id(&self, from: NodeId, to: NodeId, heuristic: impl Fn(&Node<T, W, E>) -> W) -> Result<ById<AStarPath<W>>, ShortestPathError<W>> {
+        Ok(self.by_id(self.astar(self.resolve(from)?, self.resolve(to)?, heuristic)?))
+    }
+
+    // The tree is a new graph with its own ids, the nodes are at the same indexes as in this graph
+    pub(crate) fn prim_by_id(&self, start: NodeId) -> Result<(Graph<T, W, E>, W), NodeIdError>
+        where T: Clone {
+        Ok(self.prim(self.resolve(start)?))
+    }
+
+    pub(crate) fn max_flow_by_id(&self, source: NodeId, sink: NodeId) -> Result<ById<MaxFlow<W>>, NodeIdError> {
+        Ok(self.by_id(self.max_flow(self.resolve(source)?, self.resolve(sink)?)))
+    }
+}
+
+// Panics on an invalid id, like indexing out of range
+impl<T, W, E> ops::Index<NodeId> for Graph<T, W, E> {
+    type Output = Node<T, W, E>;
+
+    fn index(&self, id: NodeId) -> &Self::Output {
+        match self.get(id) {
+            Ok(node) => node,
+            Err(err) => panic!("{err}")
+        }
+    }
+}
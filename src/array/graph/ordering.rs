@@ -0,0 +1,98 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use crate::array::graph::{Child, Graph};
+use crate::array::graph::traversal::{DfsVisitor, EdgeKind};
+
+/*
+Cycle
+ */
+#[derive(Debug, PartialEq)]
+pub struct Cycle(pub(crate) Vec<usize>); // Node indexes of the cycle, in walking order
+
+impl Display for Cycle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let cycle: Vec<String> = self.0.iter().map(|idx| idx.to_string()).collect();
+        write!(f, "graph contains a cycle: {}", cycle.join(" -> "))
+    }
+}
+
+// Keeps the current dfs path, the first back edge closes a cycle with the end of the path
+struct CycleFinder {
+    path: Vec<usize>,
+    cycle: Option<Vec<usize>>
+}
+
+impl<W, E> DfsVisitor<W, E> for CycleFinder {
+    fn discover(&mut self, node: usize, _time: usize) {
+        self.path.push(node);
+    }
+
+    fn finish(&mut self, _node: usize, _time: usize) {
+        self.path.pop();
+    }
+
+    fn edge(&mut self, _from: usize, child: &Child<W, E>, kind: EdgeKind) {
+        if kind != EdgeKind::Back || self.cycle.is_some() {
+            return;
+        }
+
+        let cycle_start = self.path.iter()
+            .position(|idx| *idx == child.idx)
+            .expect("Back edge to a node that is not on the path");
+
+        self.cycle = Some(self.path[cycle_start..].to_vec());
+    }
+}
+
+/*
+Topological sort
+ */
+impl<T, W, E> Graph<T, W, E> {
+    // Kahn's algorithm, repeatedly take a node with no edges coming in
+    pub(crate) fn topological_sort(&self) -> Result<Vec<usize>, Cycle> {
+        let mut in_degree: Vec<usize> = vec![0; self.nodes.len()];
+        let mut ready: VecDeque<usize> = VecDeque::new();
+        let mut out: Vec<usize> = Vec::with_capacity(self.nodes.len());
+
+        self.nodes.iter()
+            .flat_map(|node| node.children.iter())
+            .for_each(|c| in_degree[c.idx] += 1);
+
+        (0..self.nodes.len())
+            .filter(|idx| in_degree[*idx] == 0)
+            .for_each(|idx| ready.push_back(idx));
+
+        while let Some(current_node) = ready.pop_front() {
+            out.push(current_node);
+
+            for c in self.nodes[current_node].children.iter() {
+                in_degree[c.idx] -= 1;
+
+                if in_degree[c.idx] == 0 {
+                    ready.push_back(c.idx);
+                }
+            }
+        }
+
+        // Nodes left with edges coming in are on, or behind, a cycle
+        if out.len() != self.nodes.len() {
+            return Err(self.find_cycle().expect("Nodes left after topological sort, but no cycle found"));
+        }
+
+        Ok(out)
+    }
+
+    pub(crate) fn is_dag(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    // Back edges of a dfs from every node. In undirected graphs the edge back to the parent is not a cycle
+    pub(crate) fn find_cycle(&self) -> Option<Cycle> {
+        let mut finder = CycleFinder { path: vec![], cycle: None };
+
+        self.dfs_forest_with(&mut finder);
+
+        finder.cycle.map(Cycle)
+    }
+}
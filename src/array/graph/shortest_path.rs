@@ -0,0 +1,446 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use crate::array::graph::{Child, Graph, Node};
+use crate::array::graph::node_id::NodeIdError;
+use crate::array::graph::weight::{Ordered, Weight};
+
+/*
+Shortest paths
+ */
+#[derive(Debug)]
+pub struct ShortestPaths<W = i64> {
+    pub(crate) from: usize,
+    pub(crate) distance: Vec<Option<W>>,   // None if the node can not be reached
+    pub(crate) previous: Vec<Option<usize>>  // Node before this one on the shortest path
+}
+
+impl<W> ShortestPaths<W>
+    where W: Weight {
+    pub(crate) fn distance_to(&self, to_index: usize) -> Option<W> {
+        self.distance[to_index]
+    }
+
+    pub(crate) fn is_reachable(&self, to_index: usize) -> bool {
+        self.distance[to_index].is_some()
+    }
+
+    pub(crate) fn unreachable(&self) -> Vec<usize> {
+        (0..self.distance.len())
+            .filter(|idx| !self.is_reachable(*idx))
+            .collect()
+    }
+
+    // Walk the previous array back from the target, returns None if the target can not be reached
+    pub(crate) fn path_to(&self, to_index: usize) -> Option<Vec<usize>> {
+        if !self.is_reachable(to_index) {
+            return None;
+        }
+
+        let mut path = vec![to_index];
+        let mut current_node = to_index;
+
+        while let Some(prev) = self.previous[current_node] {
+            path.push(prev);
+            current_node = prev;
+        }
+
+        path.reverse();
+
+        Some(path)
+    }
+}
+
+impl<W> Display for ShortestPaths<W>
+    where W: Weight {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "from {}", self.from)?;
+
+        for (idx, distance) in self.distance.iter().enumerate() {
+            match (distance, self.previous[idx]) {
+                (None, _) => writeln!(f, "{idx} unreachable")?,
+                (Some(d), None) => writeln!(f, "{idx} {d} -")?,
+                (Some(d), Some(p)) => writeln!(f, "{idx} {d} {p}")?
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/*
+All pairs shortest paths
+ */
+#[derive(Debug)]
+pub struct AllPairsPaths<W = i64> {
+    pub(crate) distance: Vec<Vec<Option<W>>>,  // distance[i][j], None if j can not be reached from i
+    pub(crate) next: Vec<Vec<Option<usize>>>     // First node after i on the shortest path from i to j
+}
+
+impl<W> AllPairsPaths<W>
+    where W: Weight {
+    pub(crate) fn distance(&self, from_index: usize, to_index: usize) -> Option<W> {
+        self.distance[from_index][to_index]
+    }
+
+    // Follow the next hops from i until j is reached
+    pub(crate) fn path(&self, from_index: usize, to_index: usize) -> Option<Vec<usize>> {
+        self.distance[from_index][to_index]?;
+
+        let mut path = vec![from_index];
+        let mut current_node = from_index;
+
+        while current_node != to_index {
+            current_node = self.next[current_node][to_index]?;
+            path.push(current_node);
+        }
+
+        Some(path)
+    }
+}
+
+/*
+A* path
+ */
+#[derive(Debug)]
+pub struct AStarPath<W = i64> {
+    pub(crate) path: Vec<usize>,
+    pub(crate) cost: W,
+    pub(crate) expanded: usize  // Number of nodes taken out of the queue before the target was found
+}
+
+/*
+Errors
+ */
+#[derive(Debug, PartialEq)]
+pub enum ShortestPathError<W = i64> {
+    NegativeWeight { from: usize, to: usize, weight: W },
+    NegativeCycle(Vec<usize>),  // Node indexes of the cycle, in walking order
+    Unreachable { from: usize, to: usize },
+    InvalidNode(NodeIdError)    // The NodeId of the start or the target can not be used on the graph
+}
+
+impl<W> From<NodeIdError> for ShortestPathError<W> {
+    fn from(err: NodeIdError) -> Self {
+        ShortestPathError::InvalidNode(err)
+    }
+}
+
+impl<W> Display for ShortestPathError<W>
+    where W: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortestPathError::NegativeWeight { from, to, weight } =>
+                write!(f, "negative weight ({weight}) on the edge {from} -> {to}"),
+            ShortestPathError::NegativeCycle(cycle) => {
+                let cycle: Vec<String> = cycle.iter().map(|idx| idx.to_string()).collect();
+                write!(f, "negative cycle: {}", cycle.join(" -> "))
+            }
+            ShortestPathError::Unreachable { from, to } =>
+                write!(f, "there is no path from {from} to {to}"),
+            ShortestPathError::InvalidNode(err) => write!(f, "{err}")
+        }
+    }
+}
+
+/*
+Edge cost
+
+Distances are added with saturating_add, so a path longer than the weight type can hold stays at the largest value
+instead of overflowing.
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    // Unweighted graphs count every edge as 1
+    pub(crate) fn edge_cost(&self, child: &Child<W, E>) -> W {
+        if self.weighted {
+            child.weight
+        } else {
+            W::one()
+        }
+    }
+}
+
+/*
+Dijkstra
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    pub(crate) fn dijkstra(&self, from_index: usize) -> Result<ShortestPaths<W>, ShortestPathError<W>> {
+        // Dijkstra can not handle negative weights, use bellman_ford for those graphs
+        if let Some(err) = self.find_negative_weight() {
+            return Err(err);
+        }
+
+        let mut distance: Vec<Option<W>> = vec![None; self.nodes.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut done: Vec<bool> = vec![false; self.nodes.len()];
+        let mut queue: BinaryHeap<Reverse<(Ordered<W>, usize)>> = BinaryHeap::new();
+
+        distance[from_index] = Some(W::zero());
+        queue.push(Reverse((Ordered(W::zero()), from_index)));
+
+        while let Some(Reverse((Ordered(current_distance), current_node))) = queue.pop() {
+            // The node might be in the queue more than once, only the first (shortest) one counts
+            if done[current_node] {
+                continue;
+            }
+
+            done[current_node] = true;
+
+            for c in self.nodes[current_node].children.iter() {
+                let new_distance = current_distance.saturating_add(self.edge_cost(c));
+
+                let shorter = match distance[c.idx] {
+                    Some(d) => new_distance < d,
+                    None => true
+                };
+
+                if shorter {
+                    distance[c.idx] = Some(new_distance);
+                    previous[c.idx] = Some(current_node);
+                    queue.push(Reverse((Ordered(new_distance), c.idx)));
+                }
+            }
+        }
+
+        Ok(ShortestPaths {
+            from: from_index,
+            distance,
+            previous
+        })
+    }
+
+    fn find_negative_weight(&self) -> Option<ShortestPathError<W>> {
+        for (idx, node) in self.nodes.iter().enumerate() {
+            for c in node.children.iter() {
+                if self.edge_cost(c) < W::zero() {
+                    return Some(ShortestPathError::NegativeWeight {
+                        from: idx,
+                        to: c.idx,
+                        weight: self.edge_cost(c)
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/*
+Bellman-Ford
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    pub(crate) fn bellman_ford(&self, from_index: usize) -> Result<ShortestPaths<W>, ShortestPathError<W>> {
+        let mut distance: Vec<Option<W>> = vec![None; self.nodes.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+
+        distance[from_index] = Some(W::zero());
+
+        // After n - 1 rounds every shortest path is found, unless there is a negative cycle
+        for _ in 1..self.nodes.len() {
+            if self.relax_all(&mut distance, &mut previous).is_none() {
+                break;
+            }
+        }
+
+        // If something can still be relaxed the node is on, or behind, a negative cycle
+        if let Some(relaxed_node) = self.relax_all(&mut distance, &mut previous) {
+            return Err(ShortestPathError::NegativeCycle(
+                cycle_from(&previous, relaxed_node, self.nodes.len())
+            ));
+        }
+
+        Ok(ShortestPaths {
+            from: from_index,
+            distance,
+            previous
+        })
+    }
+
+    // Relax every edge once, returns the last node that got a shorter distance
+    fn relax_all(&self, distance: &mut [Option<W>], previous: &mut [Option<usize>]) -> Option<usize> {
+        let mut relaxed_node = None;
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let current_distance = match distance[idx] {
+                Some(d) => d,
+                None => continue
+            };
+
+            for c in node.children.iter() {
+                let new_distance = current_distance.saturating_add(self.edge_cost(c));
+
+                let shorter = match distance[c.idx] {
+                    Some(d) => new_distance < d,
+                    None => true
+                };
+
+                if shorter {
+                    distance[c.idx] = Some(new_distance);
+                    previous[c.idx] = Some(idx);
+                    relaxed_node = Some(c.idx);
+                }
+            }
+        }
+
+        relaxed_node
+    }
+}
+
+// Walking back n steps from a node relaxed in round n always ends up inside the cycle
+fn cycle_from(previous: &[Option<usize>], relaxed_node: usize, num_nodes: usize) -> Vec<usize> {
+    let mut start = relaxed_node;
+
+    for _ in 0..num_nodes {
+        start = previous[start].unwrap();
+    }
+
+    let mut cycle = vec![start];
+    let mut current_node = previous[start].unwrap();
+
+    while current_node != start {
+        cycle.push(current_node);
+        current_node = previous[current_node].unwrap();
+    }
+
+    cycle.reverse();
+
+    cycle
+}
+
+/*
+Floyd-Warshall
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    pub(crate) fn floyd_warshall(&self) -> Result<AllPairsPaths<W>, ShortestPathError<W>> {
+        let size = self.nodes.len();
+        let mut distance: Vec<Vec<Option<W>>> = vec![vec![None; size]; size];
+        let mut next: Vec<Vec<Option<usize>>> = vec![vec![None; size]; size];
+
+        for i in 0..size {
+            distance[i][i] = Some(W::zero());
+            next[i][i] = Some(i);
+        }
+
+        // Keep the cheapest edge if there are more than one between two nodes
+        self.nodes
+            .iter()
+            .enumerate()
+            .for_each(|(idx, node)| node.children
+                .iter()
+                .for_each(|child| {
+                    let cost = self.edge_cost(child);
+
+                    if distance[idx][child.idx].is_none_or(|d| cost < d) {
+                        distance[idx][child.idx] = Some(cost);
+                        next[idx][child.idx] = Some(child.idx);
+                    }
+                })
+            );
+
+        (0..size).for_each(|k| {
+            (0..size).for_each(|i| {
+                (0..size).for_each(|j| {
+                    if let (Some(ik), Some(kj)) = (distance[i][k], distance[k][j]) {
+                        let through_k = ik.saturating_add(kj);
+
+                        if distance[i][j].is_none_or(|d| through_k < d) {
+                            distance[i][j] = Some(through_k);
+                            next[i][j] = next[i][k];
+                        }
+                    }
+                });
+            });
+        });
+
+        // A node with a negative distance to itself can reach a negative cycle. The next hops are not reliable
+        // once the distances have gone negative, so bellman_ford from that node finds the cycle instead
+        if let Some(start) = (0..size).find(|i| distance[*i][*i].is_some_and(|d| d < W::zero())) {
+            return Err(self.bellman_ford(start).expect_err("Negative distance to itself, but no negative cycle found"));
+        }
+
+        Ok(AllPairsPaths {
+            distance,
+            next
+        })
+    }
+}
+
+/*
+A*
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    // The heuristic has to be an estimate of the cost to the target that is never too high,
+    // with a heuristic that always returns 0 this is the same as dijkstra.
+    // A node that is done is opened again if a shorter way to it is found later, which can happen when
+    // the heuristic drops by more than the cost of an edge.
+    pub(crate) fn astar(&self, from_index: usize, to_index: usize, heuristic: impl Fn(&Node<T, W, E>) -> W) -> Result<AStarPath<W>, ShortestPathError<W>> {
+        if let Some(err) = self.find_negative_weight() {
+            return Err(err);
+        }
+
+        let mut distance: Vec<Option<W>> = vec![None; self.nodes.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut done: Vec<bool> = vec![false; self.nodes.len()];
+        let mut queue: BinaryHeap<Reverse<(Ordered<W>, usize)>> = BinaryHeap::new();
+        let mut expanded = 0;
+
+        // Integer estimates too large for the weight type are capped, so they stay at the back of the queue
+        let estimate = |idx: usize, distance: W| Ordered(distance.saturating_add(heuristic(&self.nodes[idx])));
+
+        distance[from_index] = Some(W::zero());
+        queue.push(Reverse((estimate(from_index, W::zero()), from_index)));
+
+        while let Some(Reverse((_, current_node))) = queue.pop() {
+            if done[current_node] {
+                continue;
+            }
+
+            done[current_node] = true;
+            expanded += 1;
+
+            if current_node == to_index {
+                let paths = ShortestPaths {
+                    from: from_index,
+                    distance,
+                    previous
+                };
+
+                return Ok(AStarPath {
+                    path: paths.path_to(to_index).unwrap(),
+                    cost: paths.distance[to_index].unwrap(),
+                    expanded
+                });
+            }
+
+            let current_distance = distance[current_node].unwrap();
+
+            for c in self.nodes[current_node].children.iter() {
+                let new_distance = current_distance.saturating_add(self.edge_cost(c));
+
+                let shorter = match distance[c.idx] {
+                    Some(d) => new_distance < d,
+                    None => true
+                };
+
+                if shorter {
+                    distance[c.idx] = Some(new_distance);
+                    previous[c.idx] = Some(current_node);
+                    done[c.idx] = false;
+                    queue.push(Reverse((estimate(c.idx, new_distance), c.idx)));
+                }
+            }
+        }
+
+        Err(ShortestPathError::Unreachable {
+            from: from_index,
+            to: to_index
+        })
+    }
+}
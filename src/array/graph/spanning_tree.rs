@@ -0,0 +1,107 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use crate::array::graph::{Graph, Node};
+use crate::array::graph::weight::{Ordered, Weight};
+use crate::utils::union_find::UnionFind;
+
+/*
+Minimum spanning tree
+
+The edges are treated as undirected, the result is an undirected graph.
+If the graph is not connected the result is a spanning forest.
+ */
+impl<T, W, E> Graph<T, W, E>
+    where T: Clone, W: Weight {
+    pub(crate) fn prim(&self, start: usize) -> (Graph<T, W, E>, W) {
+        let neighbours = self.undirected_neighbours();
+        let mut tree = self.empty_copy();
+        let mut total_weight = W::zero();
+        let mut in_tree: Vec<bool> = vec![false; self.nodes.len()];
+        let mut queue: BinaryHeap<Reverse<(Ordered<W>, usize, usize)>> = BinaryHeap::new();
+
+        // Start with the given node, then every node not reached yet starts a new tree in the forest
+        for root in std::iter::once(start).chain(0..self.nodes.len()) {
+            if in_tree[root] {
+                continue;
+            }
+
+            in_tree[root] = true;
+            neighbours[root].iter().for_each(|(to, cost)| queue.push(Reverse((Ordered(*cost), root, *to))));
+
+            while let Some(Reverse((Ordered(cost), from, to))) = queue.pop() {
+                if in_tree[to] {
+                    continue;
+                }
+
+                in_tree[to] = true;
+                total_weight = total_weight + cost;
+                tree.add_edge_with_weight(from, to, cost);
+
+                neighbours[to].iter()
+                    .filter(|(next, _)| !in_tree[*next])
+                    .for_each(|(next, cost)| queue.push(Reverse((Ordered(*cost), to, *next))));
+            }
+        }
+
+        (tree, total_weight)
+    }
+
+    pub(crate) fn kruskal(&self) -> (Graph<T, W, E>, W) {
+        let mut tree = self.empty_copy();
+        let mut total_weight = W::zero();
+
+        let mut edges: Vec<(Ordered<W>, usize, usize)> = vec![];
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            node.children.iter().for_each(|c| edges.push((Ordered(self.edge_cost(c)), idx, c.idx)));
+        }
+
+        edges.sort();
+
+        // Disjoint sets, every node starts as its own set
+        let mut sets = UnionFind::new(self.nodes.len());
+
+        for (Ordered(cost), from, to) in edges {
+            // Both ends are already connected by the tree
+            if !sets.union(from, to) {
+                continue;
+            }
+
+            total_weight = total_weight + cost;
+            tree.add_edge_with_weight(from, to, cost);
+        }
+
+        (tree, total_weight)
+    }
+
+    // Same nodes and values, but no edges. Labels are not copied to the tree
+    fn empty_copy(&self) -> Graph<T, W, E> {
+        let mut graph = Graph::new_undirected();
+
+        graph.add_nodes(self.nodes.iter().map(|node| Node::new(node.val.clone())).collect());
+        graph.weighted = self.weighted;
+
+        graph
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    // Every edge in both directions with the cost of the edge
+    pub(crate) fn undirected_neighbours(&self) -> Vec<Vec<(usize, W)>> {
+        let mut neighbours: Vec<Vec<(usize, W)>> = vec![vec![]; self.nodes.len()];
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            for c in node.children.iter() {
+                neighbours[idx].push((c.idx, self.edge_cost(c)));
+
+                // Undirected graphs already have the edge back
+                if self.directed {
+                    neighbours[c.idx].push((idx, self.edge_cost(c)));
+                }
+            }
+        }
+
+        neighbours
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::VecDeque;
+use crate::array::graph::{Child, Graph, Node};
+
+/*
+Lazy traversal
+
+The iterators only walk as far as they are asked to, so a search can stop as soon as it finds what it is looking for.
+Both give the node index and the node, in the same order as Graph::dfs and Graph::bfs.
+ */
+pub struct DfsIter<'a, T, W, E> {
+    graph: &'a Graph<T, W, E>,
+    visited: Vec<bool>,
+    start: Option<usize>,
+    // (node, index in children of the next child to look at)
+    path: Vec<(usize, usize)>
+}
+
+impl<'a, T, W, E> DfsIter<'a, T, W, E> {
+    fn new(graph: &'a Graph<T, W, E>, from_index: usize) -> Self {
+        let mut dfs = DfsIter { graph, visited: vec![false; graph.nodes.len()], start: None, path: vec![] };
+        dfs.push_root(from_index);
+
+        dfs
+    }
+
+    // Starts a new tree when the current one is done. Returns false if the root is already visited
+    fn push_root(&mut self, from_index: usize) -> bool {
+        if self.visited[from_index] {
+            return false;
+        }
+
+        self.visited[from_index] = true;
+        self.start = Some(from_index);
+        self.path.push((from_index, 0));
+
+        true
+    }
+
+    // Depth of the last node returned, the root of the tree is at depth 0
+    pub(crate) fn depth(&self) -> usize {
+        self.path.len().saturating_sub(1)
+    }
+}
+
+impl<'a, T, W, E> Iterator for DfsIter<'a, T, W, E> {
+    type Item = (usize, &'a Node<T, W, E>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let graph = self.graph;
+
+        if let Some(from_index) = self.start.take() {
+            return Some((from_index, &graph.nodes[from_index]));
+        }
+
+        while let Some((current_node, pos)) = self.path.last_mut() {
+            let children = &graph.nodes[*current_node].children;
+
+            // Children before pos are already visited, so there is no need to look at them again
+            match (*pos..children.len()).find(|c| !self.visited[children[*c].idx]) {
+                Some(c) => {
+                    let child = children[c].idx;
+                    *pos = c + 1;
+
+                    self.visited[child] = true;
+                    self.path.push((child, 0));
+
+                    return Some((child, &graph.nodes[child]));
+                }
+                None => {
+                    self.path.pop();
+                }
+            }
+        }
+
+        None
+    }
+}
+
+pub struct BfsIter<'a, T, W, E> {
+    graph: &'a Graph<T, W, E>,
+    visited: Vec<bool>,
+    queue: VecDeque<usize>
+}
+
+impl<'a, T, W, E> BfsIter<'a, T, W, E> {
+    fn new(graph: &'a Graph<T, W, E>, from_index: usize) -> Self {
+        let mut visited = vec![false; graph.nodes.len()];
+        visited[from_index] = true;
+
+        BfsIter { graph, visited, queue: VecDeque::from([from_index]) }
+    }
+}
+
+impl<'a, T, W, E> Iterator for BfsIter<'a, T, W, E> {
+    type Item = (usize, &'a Node<T, W, E>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current_node = self.queue.pop_front()?;
+
+        for c in self.graph.nodes[current_node].children.iter() {
+            if !self.visited[c.idx] {
+                self.visited[c.idx] = true;
+                self.queue.push_back(c.idx);
+            }
+        }
+
+        Some((current_node, &self.graph.nodes[current_node]))
+    }
+}
+
+impl<T, W, E> Graph<T, W, E> {
+    pub(crate) fn dfs_iter(&self, from_index: usize) -> DfsIter<'_, T, W, E> {
+        DfsIter::new(self, from_index)
+    }
+
+    pub(crate) fn bfs_iter(&self, from_index: usize) -> BfsIter<'_, T, W, E> {
+        BfsIter::new(self, from_index)
+    }
+}
+
+/*
+Forests
+
+Every node is visited, also the ones that can not be reached from the first root. Each tree is a list of node indexes
+in visiting order, with the root first. The depth is the number of edges from the root of the tree, following the search.
+ */
+#[derive(Debug)]
+pub struct Forest {
+    pub(crate) trees: Vec<Vec<usize>>,
+    pub(crate) depth: Vec<Option<usize>>  // None if the node is not in any tree
+}
+
+impl Forest {
+    fn new(num_nodes: usize) -> Self {
+        Forest { trees: vec![], depth: vec![None; num_nodes] }
+    }
+
+    pub(crate) fn num_trees(&self) -> usize {
+        self.trees.len()
+    }
+
+    // Index of the tree the node is in
+    pub(crate) fn tree_of(&self, idx: usize) -> Option<usize> {
+        self.trees.iter().position(|tree| tree.contains(&idx))
+    }
+}
+
+impl<T, W, E> Graph<T, W, E> {
+    // A new tree is started from the lowest node not visited yet
+    pub(crate) fn dfs_forest(&self) -> Forest {
+        let mut forest = Forest::new(self.nodes.len());
+        let mut dfs = DfsIter { graph: self, visited: vec![false; self.nodes.len()], start: None, path: vec![] };
+
+        for root in 0..self.nodes.len() {
+            if !dfs.push_root(root) {
+                continue;
+            }
+
+            let mut tree: Vec<usize> = vec![];
+
+            while let Some((idx, _)) = dfs.next() {
+                tree.push(idx);
+                forest.depth[idx] = Some(dfs.depth());
+            }
+
+            forest.trees.push(tree);
+        }
+
+        forest
+    }
+
+    pub(crate) fn bfs_forest(&self) -> Forest {
+        let mut forest = Forest::new(self.nodes.len());
+
+        for root in 0..self.nodes.len() {
+            self.bfs_into(&[root], &mut forest);
+        }
+
+        forest
+    }
+
+    // All the sources start at level 0, and every node belongs to the tree of the source that reached it first.
+    // Nodes that can not be reached from any of the sources are not in the forest
+    pub(crate) fn bfs_from_many(&self, sources: &[usize]) -> Forest {
+        let mut forest = Forest::new(self.nodes.len());
+        self.bfs_into(sources, &mut forest);
+
+        forest
+    }
+
+    // Sources already in the forest are skipped, the others start a new tree each
+    fn bfs_into(&self, sources: &[usize], forest: &mut Forest) {
+        // (node, index of its tree)
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+
+        for source in sources {
+            if forest.depth[*source].is_none() {
+                forest.depth[*source] = Some(0);
+                queue.push_back((*source, forest.trees.len()));
+                forest.trees.push(vec![]);
+            }
+        }
+
+        while let Some((current_node, tree)) = queue.pop_front() {
+            let level = forest.depth[current_node].unwrap() + 1;
+            forest.trees[tree].push(current_node);
+
+            for c in self.nodes[current_node].children.iter() {
+                if forest.depth[c.idx].is_none() {
+                    forest.depth[c.idx] = Some(level);
+                    queue.push_back((c.idx, tree));
+                }
+            }
+        }
+    }
+}
+
+/*
+Dfs visitor
+
+Every node gets a discover time when the search first reaches it, and a finish time when all of its children are done.
+Both come from the same clock, starting at 0, so a node is a descendant of another exactly when its times are inside the other's.
+
+Every edge the search looks at is classified:
+- Tree: leads to a new node
+- Back: leads to a node on the current path, so there is a cycle
+- Forward: leads to a finished descendant
+- Cross: leads to a finished node in another branch
+Undirected graphs only have tree and back edges. The edge back to the parent is not reported, and every other edge is reported once.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeKind {
+    Tree,
+    Back,
+    Forward,
+    Cross
+}
+
+// Every method does nothing by default, so a visitor only implements what it needs
+pub trait DfsVisitor<W, E> {
+    fn discover(&mut self, _node: usize, _time: usize) {}
+
+    fn finish(&mut self, _node: usize, _time: usize) {}
+
+    fn edge(&mut self, _from: usize, _child: &Child<W, E>, _kind: EdgeKind) {}
+}
+
+impl<T, W, E> Graph<T, W, E> {
+    pub(crate) fn dfs_with(&self, from_index: usize, visitor: &mut impl DfsVisitor<W, E>) {
+        let mut discovered: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut finished: Vec<bool> = vec![false; self.nodes.len()];
+
+        self.dfs_visit(from_index, visitor, &mut discovered, &mut finished, &mut 0);
+    }
+
+    // Every node is visited, a new tree is started from the lowest node not discovered yet. The clock keeps going between the trees
+    pub(crate) fn dfs_forest_with(&self, visitor: &mut impl DfsVisitor<W, E>) {
+        let mut discovered: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        let mut finished: Vec<bool> = vec![false; self.nodes.len()];
+        let mut time = 0;
+
+        for root in 0..self.nodes.len() {
+            if discovered[root].is_none() {
+                self.dfs_visit(root, visitor, &mut discovered, &mut finished, &mut time);
+            }
+        }
+    }
+
+    fn dfs_visit(&self, from_index: usize, visitor: &mut impl DfsVisitor<W, E>,
+                 discovered: &mut [Option<usize>], finished: &mut [bool], time: &mut usize) {
+        // (node, index in children of the next child to look at, the edge back to the parent has been skipped)
+        let mut path: Vec<(usize, usize, bool)> = vec![(from_index, 0, self.directed)];
+
+        discovered[from_index] = Some(*time);
+        visitor.discover(from_index, *time);
+        *time += 1;
+
+        while let Some(&(current_node, pos, skipped_parent)) = path.last() {
+            let Some(c) = self.nodes[current_node].children.get(pos) else {
+                path.pop();
+
+                finished[current_node] = true;
+                visitor.finish(current_node, *time);
+                *time += 1;
+
+                continue;
+            };
+
+            let last = path.len() - 1;
+            path[last].1 += 1;
+
+            let kind = match discovered[c.idx] {
+                None => EdgeKind::Tree,
+                Some(_) if !self.directed && finished[c.idx] => continue, // Already reported from the other end
+                Some(_) if !skipped_parent && last > 0 && path[last - 1].0 == c.idx => {
+                    path[last].2 = true;
+                    continue;
+                }
+                Some(_) if !finished[c.idx] => EdgeKind::Back,
+                Some(time) if time > discovered[current_node].unwrap() => EdgeKind::Forward,
+                Some(_) => EdgeKind::Cross
+            };
+
+            visitor.edge(current_node, c, kind);
+
+            if kind == EdgeKind::Tree {
+                discovered[c.idx] = Some(*time);
+                visitor.discover(c.idx, *time);
+                *time += 1;
+
+                path.push((c.idx, 0, self.directed));
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/*
+Weight
+
+The type of the edge weights. Graph, Node and Child default to i64, every algorithm works with the other types as well.
+Floats are ordered with total_cmp, so a NaN weight does not break the queues, it is just larger than everything else.
+ */
+pub trait Weight: Copy + PartialEq + PartialOrd + Default + Debug + Display + FromStr + Add<Output = Self> + Sub<Output = Self> {
+    const INTEGER: bool;
+
+    fn zero() -> Self;
+    fn one() -> Self;
+
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    // Integers stop at their smallest or largest value instead of overflowing
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+macro_rules! impl_weight {
+    (integer: $($int:ty),*; float: $($float:ty),*) => {
+        $(
+            impl Weight for $int {
+                const INTEGER: bool = true;
+
+                fn zero() -> Self {
+                    0
+                }
+
+                fn one() -> Self {
+                    1
+                }
+
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    self.cmp(other)
+                }
+
+                fn saturating_add(self, other: Self) -> Self {
+                    <$int>::saturating_add(self, other)
+                }
+            }
+        )*
+
+        $(
+            impl Weight for $float {
+                const INTEGER: bool = false;
+
+                fn zero() -> Self {
+                    0.0
+                }
+
+                fn one() -> Self {
+                    1.0
+                }
+
+                fn total_cmp(&self, other: &Self) -> Ordering {
+                    <$float>::total_cmp(self, other)
+                }
+
+                fn saturating_add(self, other: Self) -> Self {
+                    self + other
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(
+    integer: i32, i64, u32, u64, usize;
+    float: f32, f64
+);
+
+// Weight with a total order, so floats can be used in a BinaryHeap or sorted
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Ordered<W>(pub(crate) W);
+
+impl<W: Weight> PartialEq for Ordered<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: Weight> Eq for Ordered<W> {}
+
+impl<W: Weight> PartialOrd for Ordered<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: Weight> Ord for Ordered<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
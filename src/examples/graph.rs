@@ -2,9 +2,9 @@ use crate::array::graph::{Child, Graph, Node};
 use crate::utils::read_file::FileReader;
 
 fn main() {
-    let mut node0 = Node::new("kake");
-    let mut node1 = Node::new("eple");
-    let mut node2 = Node::new("potet");
+    let mut node0: Node<&str> = Node::new("kake");
+    let mut node1: Node<&str> = Node::new("eple");
+    let mut node2: Node<&str> = Node::new("potet");
 
     let node3 = Node::new_with_children("poteter", vec![
         Child::new_with_weight(0, 1),
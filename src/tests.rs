@@ -1,3 +1,5 @@
 pub mod binary_tree_array_tests;
 pub mod avl_tree_test;
 mod graph_test;
+#[cfg(feature = "serde")]
+mod serialize_test;
\ No newline at end of file
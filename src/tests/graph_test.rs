@@ -1,7 +1,22 @@
 #[cfg(test)]
 mod tests {
+    use std::fs;
     use std::fs::File;
+    use std::hint::black_box;
+    use std::str::FromStr;
+    use std::time::Instant;
     use crate::array::graph::{Child, Graph, Node};
+    use crate::array::graph::csr::CsrGraph;
+    use crate::array::graph::label::NoLabel;
+    use crate::array::graph::node_id::{NodeId, NodeIdError};
+    use crate::array::graph::shortest_path::ShortestPathError;
+    use crate::array::graph::traversal::{DfsVisitor, EdgeKind};
+    use crate::array::graph::ordering::Cycle;
+    use crate::utils::bit_matrix::BitMatrix;
+    use crate::utils::union_find::UnionFind;
+    use crate::utils::dot::DotOptions;
+    use crate::utils::formats::{read_dimacs, read_edge_list, read_matrix_market};
+    use crate::utils::read_file::{read_graph, FileReader, GraphParseErrorKind};
 
     #[test]
     fn basic_insertion() {
@@ -73,6 +88,138 @@ mod tests {
         assert_eq!(path, vec![2, 1, 0, 3, 4, 6, 5])
     }
 
+    #[test]
+    fn traversal_iter() {
+        let graph = gen_graph();
+
+        assert_eq!(graph.dfs_iter(2).map(|(idx, _)| idx).collect::<Vec<usize>>(), vec![2, 1, 0, 3, 4, 6, 5]);
+        assert_eq!(graph.bfs_iter(2).map(|(idx, _)| idx).collect::<Vec<usize>>(), vec![2, 1, 3, 4, 0, 6, 5]);
+
+        // Stopping early does not walk the rest of the graph
+        let mut dfs = graph.dfs_iter(2);
+
+        assert_eq!(dfs.find(|(_, node)| node.val == 0).map(|(idx, _)| idx), Some(0));
+        assert_eq!(dfs.next().map(|(idx, _)| idx), Some(3));
+
+        assert_eq!(graph.bfs_iter(5).take(2).map(|(_, node)| node.val).collect::<Vec<u8>>(), vec![5, 6]);
+        assert_eq!(graph.dfs_iter(5).count(), 7);
+    }
+
+    #[test]
+    fn forest() {
+        let graph: Graph<char> = Graph::read_file("src/files/graf_13.txt", false);
+
+        // B has no children
+        assert_eq!(graph.dfs(1).len(), 1);
+
+        let dfs = graph.dfs_forest();
+
+        assert_eq!(dfs.trees, vec![vec![0, 1, 5, 4, 3, 6, 2, 9, 10, 11, 12], vec![7, 8]]);
+        assert_eq!(dfs.depth, [0, 1, 2, 3, 2, 1, 1, 0, 1, 2, 3, 3, 4].map(Some));
+        assert_eq!(dfs.tree_of(8), Some(1));
+
+        let bfs = graph.bfs_forest();
+
+        assert_eq!(bfs.trees, vec![vec![0, 1, 5, 6, 4, 2, 9, 3, 10, 11, 12], vec![7, 8]]);
+        assert_eq!(bfs.depth, [0, 1, 2, 3, 2, 1, 1, 0, 1, 2, 3, 3, 3].map(Some));
+
+        // Every source is a root, and the nodes go to the source that gets to them first
+        let many = graph.bfs_from_many(&[1, 9, 4, 9]);
+
+        assert_eq!(many.num_trees(), 3);
+        assert_eq!(many.trees, vec![vec![1], vec![9, 10, 11, 12, 6, 2, 0], vec![4, 3, 5]]);
+        assert_eq!(many.depth, vec![Some(4), Some(0), Some(3), Some(1), Some(0), Some(2), Some(2), None, None, Some(0), Some(1), Some(1), Some(1)]);
+        assert_eq!(many.tree_of(7), None);
+
+        assert_eq!(Graph::<char>::new().dfs_forest().num_trees(), 0);
+    }
+
+    struct Recorder {
+        discover: Vec<Option<usize>>,
+        finish: Vec<Option<usize>>,
+        edges: Vec<(usize, usize, EdgeKind)>
+    }
+
+    impl Recorder {
+        fn new(num_nodes: usize) -> Self {
+            Recorder { discover: vec![None; num_nodes], finish: vec![None; num_nodes], edges: vec![] }
+        }
+    }
+
+    impl DfsVisitor<i64, NoLabel> for Recorder {
+        fn discover(&mut self, node: usize, time: usize) {
+            self.discover[node] = Some(time);
+        }
+
+        fn finish(&mut self, node: usize, time: usize) {
+            self.finish[node] = Some(time);
+        }
+
+        fn edge(&mut self, from: usize, child: &Child, kind: EdgeKind) {
+            self.edges.push((from, child.idx, kind));
+        }
+    }
+
+    #[test]
+    fn dfs_with_directed() {
+        let graph: Graph<u8> = Graph::from(vec![
+            (0, vec![1, 2, 3]),
+            (1, vec![2]),
+            (2, vec![0]),
+            (3, vec![2]),
+            (4, vec![0])
+        ]);
+
+        let mut recorder = Recorder::new(5);
+        graph.dfs_with(0, &mut recorder);
+
+        assert_eq!(recorder.edges, vec![
+            (0, 1, EdgeKind::Tree),
+            (1, 2, EdgeKind::Tree),
+            (2, 0, EdgeKind::Back),
+            (0, 2, EdgeKind::Forward),
+            (0, 3, EdgeKind::Tree),
+            (3, 2, EdgeKind::Cross)
+        ]);
+
+        assert_eq!(recorder.discover, vec![Some(0), Some(1), Some(2), Some(5), None]);
+        assert_eq!(recorder.finish, vec![Some(7), Some(4), Some(3), Some(6), None]);
+    }
+
+    #[test]
+    fn dfs_with_undirected() {
+        let mut graph = gen_graph();
+        graph.directed = false;
+
+        let mut recorder = Recorder::new(7);
+        graph.dfs_with(2, &mut recorder);
+
+        // Every edge once, and the only edge that is not in the tree closes the cycle 1 -> 3 -> 2
+        assert_eq!(recorder.edges, vec![
+            (2, 1, EdgeKind::Tree),
+            (1, 0, EdgeKind::Tree),
+            (1, 3, EdgeKind::Tree),
+            (3, 2, EdgeKind::Back),
+            (2, 4, EdgeKind::Tree),
+            (4, 6, EdgeKind::Tree),
+            (6, 5, EdgeKind::Tree)
+        ]);
+
+        assert_eq!(recorder.discover, vec![Some(2), Some(1), Some(0), Some(4), Some(7), Some(9), Some(8)]);
+        assert_eq!(recorder.finish, vec![Some(3), Some(6), Some(13), Some(5), Some(12), Some(10), Some(11)]);
+
+        // A parallel edge is a cycle of two nodes
+        let mut graph: Graph<u8> = Graph::new_undirected();
+        graph.add_nodes(vec![Node::new(0), Node::new(1)]);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 1);
+
+        let mut recorder = Recorder::new(2);
+        graph.dfs_with(0, &mut recorder);
+
+        assert_eq!(recorder.edges, vec![(0, 1, EdgeKind::Tree), (1, 0, EdgeKind::Back)]);
+    }
+
     #[test]
     fn neighborhood() {
         let graph = gen_graph();
@@ -113,10 +260,46 @@ mod tests {
         assert_eq!(aprp, solution_aprp)
     }
 
+    #[test]
+    fn warshall_bit_matrix() {
+        // More than one word per row, and a row length that is not a multiple of 64
+        let graph = gen_large_graph(150, 1);
+        let reachable = graph.warshall_bit_matrix();
+
+        // The old one cell at a time version
+        let mut solution = graph.get_neighborhood_matrix();
+
+        for k in 0..150 {
+            for i in 0..150 {
+                for j in 0..150 {
+                    solution[i][j] = solution[i][j] || (solution[i][k] && solution[k][j]);
+                }
+            }
+        }
+
+        assert_eq!(reachable.words_per_row, 3);
+        assert_eq!(reachable.to_vec(), solution);
+        assert_eq!(BitMatrix::from(&solution), reachable);
+        assert_eq!((0..150).map(|i| reachable.count_row(i)).collect::<Vec<usize>>(),
+                   solution.iter().map(|row| row.iter().filter(|cell| **cell).count()).collect::<Vec<usize>>());
+
+        let mut matrix = BitMatrix::new(70);
+        matrix.set(1, 65, true);
+        matrix.set(2, 3, true);
+        matrix.or_row(2, 1);
+
+        assert!(matrix.get(2, 65) && matrix.get(2, 3) && !matrix.get(1, 3));
+
+        matrix.set(2, 65, false);
+
+        assert_eq!(matrix.row(2), &[1 << 3, 0]);
+        assert_eq!(Vec::<Vec<bool>>::from(matrix.clone()), matrix.to_vec());
+    }
+
     #[test]
     fn unweighted_from_file() {
         let file = File::open("/Users/tobiashallingstad/Prog/Languages/Rust/Algoritmes/src/files/graf_13.txt").unwrap();
-        let graph: Graph<char> = Graph::from(file);
+        let graph: Graph<char> = Graph::try_from(file).unwrap();
 
         let correct_graph = vec![
             ('A', vec![1, 5, 6]),
@@ -146,7 +329,7 @@ mod tests {
     #[test]
     fn weighed_from_file() {
         let file = File::open("/Users/tobiashallingstad/Prog/Languages/Rust/Algoritmes/src/files/vgraf_13.txt").unwrap();
-        let graph: Graph<char> = Graph::from(file);
+        let graph: Graph<char> = Graph::try_from(file).unwrap();
 
         let correct_graph = vec![
             ('A', vec![(1, 1), (5, 2), (6, 4)]),
@@ -165,7 +348,7 @@ mod tests {
         ];
 
         graph.nodes.iter().enumerate().for_each(|(idx, node)| {
-            let children: Vec<(usize, u32)> = node.children.iter()
+            let children: Vec<(usize, i64)> = node.children.iter()
                 .map(|child| (child.idx, child.weight))
                 .collect();
 
@@ -175,4 +358,1574 @@ mod tests {
             assert_eq!(children, correct_values.1);
         });
     }
-}
\ No newline at end of file
+
+    /*
+    Shortest paths
+     */
+    #[test]
+    fn dijkstra() {
+        let graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let paths = graph.dijkstra(0).unwrap();
+
+        assert_eq!(paths.distance, vec![
+            Some(0), Some(1), Some(5), Some(6), Some(4), Some(2), Some(4),
+            None, None, Some(5), Some(6), Some(8), Some(7)
+        ]);
+
+        assert_eq!(paths.path_to(3), Some(vec![0, 5, 4, 3]));
+        assert_eq!(paths.path_to(12), Some(vec![0, 6, 9, 12]));
+        assert_eq!(paths.path_to(0), Some(vec![0]));
+        assert_eq!(paths.path_to(7), None);
+        assert_eq!(paths.unreachable(), vec![7, 8]);
+    }
+
+    #[test]
+    fn shortest_paths_near_max_weights() {
+        // The paths are longer than i64 can hold, so the distances stay at i64::MAX
+        let mut graph: Graph<char> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from(('a', vec![(1, i64::MAX)])),
+            Node::from(('b', vec![(2, 1)])),
+            Node::from(('c', vec![(3, i64::MAX)])),
+            Node::new('d')
+        ]);
+        graph.weighted = true;
+
+        assert_eq!(graph.dijkstra(0).unwrap().distance_to(3), Some(i64::MAX));
+        assert_eq!(graph.bellman_ford(0).unwrap().distance_to(2), Some(i64::MAX));
+        assert_eq!(graph.floyd_warshall().unwrap().distance(0, 3), Some(i64::MAX));
+        assert_eq!(graph.astar(0, 3, |_| 0).unwrap().cost, i64::MAX);
+        assert_eq!(graph.floyd_warshall().unwrap().distance(1, 3), Some(i64::MAX));
+
+        let mut graph: Graph<char, u32> = Graph::new();
+        graph.add_nodes(vec![Node::from(('a', vec![(1, 3_000_000_000)])), Node::from(('b', vec![(2, 3_000_000_000)])), Node::new('c')]);
+        graph.weighted = true;
+
+        assert_eq!(graph.dijkstra(0).unwrap().distance_to(2), Some(u32::MAX));
+        assert_eq!(graph.bellman_ford(0).unwrap().distance_to(2), Some(u32::MAX));
+        assert_eq!(graph.floyd_warshall().unwrap().distance(0, 2), Some(u32::MAX));
+        assert_eq!(graph.astar(0, 2, |_| 0).unwrap().path, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn dijkstra_string_graph() {
+        let graph: Graph<String> = Graph::read_file("src/files/TestGraphDataV2.txt", true);
+        let paths = graph.dijkstra(0).unwrap();
+
+        assert_eq!(paths.distance, vec![Some(0), Some(2), Some(3), Some(1), Some(3), Some(6), Some(5)]);
+
+        let path: Vec<&str> = paths.path_to(5).unwrap()
+            .iter()
+            .map(|idx| graph.nodes[*idx].val.as_str())
+            .collect();
+
+        assert_eq!(path, vec!["V0", "V3", "V6", "V5"]);
+
+        let paths = graph.dijkstra(5).unwrap();
+
+        assert_eq!(paths.unreachable(), vec![0, 1, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn dijkstra_unweighted() {
+        let graph = gen_graph();
+        let paths = graph.dijkstra(0).unwrap();
+
+        assert_eq!(paths.distance, vec![Some(0), Some(1), Some(2), Some(2), Some(3), Some(5), Some(4)]);
+        assert_eq!(paths.path_to(5), Some(vec![0, 1, 2, 4, 6, 5]));
+    }
+
+    #[test]
+    fn dijkstra_negative_weight() {
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphDataV3.txt", true);
+
+        assert_eq!(graph.dijkstra(0).unwrap_err(), ShortestPathError::NegativeWeight { from: 1, to: 3, weight: -3 });
+    }
+
+    #[test]
+    fn bellman_ford() {
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphDataV3.txt", true);
+        let paths = graph.bellman_ford(0).unwrap();
+
+        assert_eq!(paths.distance, vec![Some(0), Some(3), Some(5), Some(0), Some(2)]);
+        assert_eq!(paths.path_to(4), Some(vec![0, 2, 1, 3, 4]));
+
+        // Same result as dijkstra when there are no negative weights
+        let graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+
+        assert_eq!(graph.bellman_ford(0).unwrap().distance, graph.dijkstra(0).unwrap().distance);
+    }
+
+    #[test]
+    fn bellman_ford_negative_cycle() {
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphDataV4.txt", true);
+
+        let cycle = match graph.bellman_ford(0) {
+            Err(ShortestPathError::NegativeCycle(cycle)) => cycle,
+            other => panic!("Expected a negative cycle, got {other:?}")
+        };
+
+        let mut sorted_cycle = cycle.clone();
+        sorted_cycle.sort();
+
+        assert_eq!(sorted_cycle, vec![1, 2, 3]);
+
+        // Every step in the cycle has to be an edge in the graph
+        for (i, idx) in cycle.iter().enumerate() {
+            let next = cycle[(i + 1) % cycle.len()];
+            assert!(graph.nodes[*idx].children.iter().any(|c| c.idx == next));
+        }
+    }
+
+    #[test]
+    fn bellman_ford_unreachable_negative_cycle() {
+        let mut graph: Graph<u8> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from((0, vec![(1, 1)])),
+            Node::new(1),
+            Node::from((2, vec![(3, -2)])),
+            Node::from((3, vec![(2, 1)]))
+        ]);
+        graph.weighted = true;
+
+        let paths = graph.bellman_ford(0).unwrap();
+
+        assert_eq!(paths.distance, vec![Some(0), Some(1), None, None]);
+        assert!(graph.bellman_ford(2).is_err());
+    }
+
+    #[test]
+    fn floyd_warshall() {
+        let graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let all_pairs = graph.floyd_warshall().unwrap();
+
+        // Every row should be the same as running dijkstra from that node
+        for i in 0..graph.nodes.len() {
+            assert_eq!(all_pairs.distance[i], graph.dijkstra(i).unwrap().distance);
+        }
+
+        assert_eq!(all_pairs.path(0, 3), Some(vec![0, 5, 4, 3]));
+        assert_eq!(all_pairs.path(0, 12), Some(vec![0, 6, 9, 12]));
+        assert_eq!(all_pairs.path(4, 4), Some(vec![4]));
+        assert_eq!(all_pairs.path(0, 7), None);
+
+        // Reachable pairs should match warshall, and the graph is still usable
+        let reachable = graph.warshall();
+
+        for (i, row) in reachable.iter().enumerate() {
+            for (j, can_reach) in row.iter().enumerate() {
+                assert_eq!(all_pairs.distance(i, j).is_some(), *can_reach);
+            }
+        }
+    }
+
+    #[test]
+    fn floyd_warshall_negative() {
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphDataV3.txt", true);
+        let all_pairs = graph.floyd_warshall().unwrap();
+
+        assert_eq!(all_pairs.distance[0], graph.bellman_ford(0).unwrap().distance);
+        assert_eq!(all_pairs.path(0, 4), Some(vec![0, 2, 1, 3, 4]));
+
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphDataV4.txt", true);
+
+        assert!(matches!(graph.floyd_warshall(), Err(ShortestPathError::NegativeCycle(_))));
+
+        // The cycle 1 -> 2 -> 1 does not go through 0, the lowest node with a negative distance to itself
+        let mut graph: Graph<u8> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from((0, vec![(1, 1)])),
+            Node::from((1, vec![(2, -5)])),
+            Node::from((2, vec![(1, 1), (3, 1)])),
+            Node::from((3, vec![(0, 1)]))
+        ]);
+        graph.weighted = true;
+
+        assert_eq!(graph.floyd_warshall().unwrap_err(), ShortestPathError::NegativeCycle(vec![1, 2]));
+        assert_eq!(graph.bellman_ford(0).unwrap_err(), ShortestPathError::NegativeCycle(vec![1, 2]));
+    }
+
+    /*
+    Minimum spanning tree
+     */
+    fn tree_edges(graph: &Graph<char>) -> Vec<(usize, usize, i64)> {
+        let mut edges: Vec<(usize, usize, i64)> = graph.nodes.iter()
+            .enumerate()
+            .flat_map(|(idx, node)| node.children.iter().map(move |c| (idx, c.idx, c.weight)))
+            .filter(|(from, to, _)| from < to)
+            .collect();
+
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn minimum_spanning_tree() {
+        let graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+
+        let (prim_tree, prim_weight) = graph.prim(0);
+        let (kruskal_tree, kruskal_weight) = graph.kruskal();
+
+        assert_eq!(prim_weight, 17);
+        assert_eq!(kruskal_weight, 17);
+
+        assert_eq!(tree_edges(&prim_tree).len(), 12);
+        assert_eq!(tree_edges(&kruskal_tree).len(), 12);
+
+        // Node values are kept, and every node is reachable in the tree
+        for tree in [&prim_tree, &kruskal_tree] {
+            assert!(tree.weighted);
+            assert!(!tree.directed);
+            assert_eq!(tree.asymmetric_edges(), vec![]);
+            assert_eq!(tree.nodes.iter().map(|n| n.val).collect::<String>(), "ABCDEFGHIJKLM");
+            assert_eq!(tree.dfs(0).len(), 13);
+        }
+
+        // The original graph is untouched
+        assert_eq!(graph.nodes[0].children.len(), 3);
+    }
+
+    #[test]
+    fn minimum_spanning_forest() {
+        let mut graph: Graph<char> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from(('a', vec![(1, 3)])),
+            Node::from(('b', vec![(0, 2)])),
+            Node::from(('c', vec![(3, 1)])),
+            Node::new('d'),
+            Node::new('e')
+        ]);
+        graph.weighted = true;
+
+        let (prim_tree, prim_weight) = graph.prim(2);
+        let (kruskal_tree, kruskal_weight) = graph.kruskal();
+
+        assert_eq!(prim_weight, 3);
+        assert_eq!(kruskal_weight, 3);
+        assert_eq!(tree_edges(&prim_tree), vec![(0, 1, 2), (2, 3, 1)]);
+        assert_eq!(tree_edges(&kruskal_tree), vec![(0, 1, 2), (2, 3, 1)]);
+    }
+
+    /*
+    Topological sort
+     */
+    fn is_cycle<T>(graph: &Graph<T>, cycle: &[usize]) -> bool {
+        cycle.iter().enumerate().all(|(i, idx)| {
+            let next = cycle[(i + 1) % cycle.len()];
+            graph.nodes[*idx].children.iter().any(|c| c.idx == next)
+        })
+    }
+
+    #[test]
+    fn topological_sort() {
+        /*
+        a -> b -> d
+        |    |
+        v    v
+        c -> e    f
+         */
+        let graph = Graph::from(vec![
+            ('a', vec![1, 2]),
+            ('b', vec![3, 4]),
+            ('c', vec![4]),
+            ('d', vec![]),
+            ('e', vec![]),
+            ('f', vec![])
+        ]);
+
+        let order = graph.topological_sort().unwrap();
+
+        assert_eq!(order, vec![0, 5, 1, 2, 3, 4]);
+        assert!(graph.is_dag());
+        assert_eq!(graph.find_cycle(), None);
+    }
+
+    #[test]
+    fn topological_sort_cycle() {
+        let file = File::open("src/files/graf_13.txt").unwrap();
+        let graph: Graph<char> = Graph::try_from(file).unwrap();
+
+        let cycle = graph.topological_sort().unwrap_err();
+
+        assert!(is_cycle(&graph, &cycle.0));
+        assert!(!graph.is_dag());
+
+        // a -> b -> c -> d -> e -> a
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphData1.txt", false);
+
+        assert_eq!(graph.find_cycle(), Some(Cycle(vec![0, 1, 2, 3, 4])));
+
+        let graph = Graph::from(vec![('a', vec![1]), ('b', vec![1])]);
+
+        assert_eq!(graph.find_cycle(), Some(Cycle(vec![1])));
+    }
+
+    #[test]
+    fn find_cycle_star() {
+        // Every child of the center is only looked at once, a search that starts over at the first child
+        // after every leaf would take minutes here
+        let mut graph: Graph<usize> = Graph::new_with_size(200_000);
+        (1..200_000).for_each(|leaf| graph.add_edge(0, leaf));
+
+        assert!(graph.is_dag());
+
+        graph.add_edge(199_999, 0);
+
+        assert_eq!(graph.find_cycle(), Some(Cycle(vec![0, 199_999])));
+
+        // Undirected edges do not make a cycle on their own
+        let mut graph: Graph<char> = Graph::new_undirected();
+        graph.add_nodes(vec![Node::new('a'), Node::new('b'), Node::new('c')]);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+
+        assert_eq!(graph.find_cycle(), None);
+
+        graph.add_edge(2, 0);
+
+        assert_eq!(graph.find_cycle(), Some(Cycle(vec![0, 1, 2])));
+    }
+
+    /*
+    Strongly connected components
+     */
+    #[test]
+    fn strongly_connected_components() {
+        let file = File::open("src/files/graf_13.txt").unwrap();
+        let graph: Graph<char> = Graph::try_from(file).unwrap();
+
+        let condensation = graph.condensation();
+
+        let mut components: Vec<String> = condensation.nodes.iter()
+            .map(|node| node.val.iter().map(|idx| graph.nodes[*idx].val).collect())
+            .collect();
+        components.sort();
+
+        assert_eq!(components, vec!["ACGJLM", "B", "DEF", "HI", "K"]);
+        assert!(condensation.is_dag());
+
+        // Edges only go from lower to higher component ids
+        for (idx, node) in condensation.nodes.iter().enumerate() {
+            assert!(node.children.iter().all(|c| c.idx > idx));
+        }
+    }
+
+    #[test]
+    fn strongly_connected_components_warshall() {
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphData3.txt", false);
+        let component = graph.strongly_connected_components();
+        let reachable = graph.warshall();
+
+        for (i, row) in reachable.iter().enumerate() {
+            for (j, can_reach) in row.iter().enumerate() {
+                assert_eq!(component[i] == component[j], *can_reach && reachable[j][i]);
+            }
+        }
+    }
+
+    /*
+    Weakly connected components
+     */
+    #[test]
+    fn union_find() {
+        let mut sets = UnionFind::new(6);
+
+        assert_eq!(sets.num_sets(), 6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(4, 3));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(3, 0));
+
+        assert!(sets.connected(0, 3));
+        assert!(!sets.connected(2, 5));
+        assert_eq!(sets.num_sets(), 3);
+        assert_eq!(sets.set_ids(), vec![0, 0, 1, 0, 0, 2]);
+
+        // Every index on the way to the root now points directly at it
+        let root = sets.find(3);
+        assert!([0, 1, 3, 4].iter().all(|idx| sets.parent[*idx] == root));
+
+        assert_eq!(sets.add(), 6);
+        assert!(sets.union(6, 2));
+        assert_eq!((sets.len(), sets.num_sets()), (7, 3));
+    }
+
+    #[test]
+    fn weakly_connected_components() {
+        let graph: Graph<u8> = Graph::from(vec![
+            (0, vec![1]),
+            (1, vec![]),
+            (2, vec![1]),
+            (3, vec![4]),
+            (4, vec![]),
+            (5, vec![])
+        ]);
+
+        assert_eq!(graph.weakly_connected_components(), vec![0, 0, 0, 1, 1, 2]);
+        assert_eq!(graph.component_count(), 3);
+
+        // H and I only have an edge into the rest of the graph, so it is one component
+        let graph: Graph<char> = Graph::read_file("src/files/graf_13.txt", false);
+
+        assert_eq!(graph.component_count(), 1);
+        assert_eq!(graph.condensation().nodes.len(), 5);
+        assert_eq!(Graph::<char>::new().component_count(), 0);
+    }
+
+    #[test]
+    fn kruskal_union_find() {
+        let graph: Graph<char> = Graph::read_file_undirected("src/files/TestGraphData2.txt", false);
+        let (tree, _) = graph.kruskal();
+
+        // A spanning forest has one edge less than nodes for every component
+        let num_edges: usize = tree.nodes.iter().map(|node| node.children.len()).sum::<usize>() / 2;
+
+        assert_eq!(num_edges, graph.nodes.len() - graph.component_count());
+        assert_eq!(tree.component_count(), graph.component_count());
+    }
+
+    /*
+    Undirected
+     */
+    #[test]
+    fn undirected_add_edge() {
+        let mut graph: Graph<char> = Graph::new_undirected();
+        graph.add_nodes(vec![Node::new('a'), Node::new('b'), Node::new('c')]);
+        graph.weighted = true;
+
+        graph.add_edge_with_weight(0, 1, 4);
+        graph.add_edge_with_weight(1, 2, 2);
+        graph.add_edge_with_weight(2, 2, 1);
+
+        let children: Vec<Vec<(usize, i64)>> = graph.nodes.iter()
+            .map(|node| node.children.iter().map(|c| (c.idx, c.weight)).collect())
+            .collect();
+
+        assert_eq!(children, vec![
+            vec![(1, 4)],
+            vec![(0, 4), (2, 2)],
+            vec![(1, 2), (2, 1)]
+        ]);
+        assert_eq!(graph.validate_undirected(), Ok(()));
+        assert!(graph.to_string().starts_with("3 undirected\n"));
+
+        // Directed graphs only get the one edge
+        let mut graph: Graph<char> = Graph::new();
+        graph.add_nodes(vec![Node::new('a'), Node::new('b')]);
+        graph.add_edge(0, 1);
+
+        assert_eq!(graph.nodes[1].children.len(), 0);
+        assert_eq!(graph.validate_undirected(), Err(vec![(0, 1)]));
+    }
+
+    #[test]
+    fn undirected_from_file() {
+        let graph: Graph<char> = Graph::read_file_undirected("src/files/TestGraphData2.txt", false);
+
+        assert!(!graph.directed);
+        assert_eq!(graph.asymmetric_edges(), vec![]);
+
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphData1.txt", false);
+
+        assert_eq!(graph.asymmetric_edges(), vec![(0, 1), (1, 2), (1, 3), (2, 3), (3, 4), (4, 0)]);
+
+        let graph: Graph<char> = Graph::read_file_undirected("src/files/TestGraphDataV1.txt", true);
+
+        assert!(graph.weighted);
+        assert_eq!(graph.asymmetric_edges(), vec![]);
+
+        // Same edges, but not the same weight
+        let mut graph: Graph<char> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from(('a', vec![(1, 2)])),
+            Node::from(('b', vec![(0, 3)]))
+        ]);
+        graph.weighted = true;
+
+        assert_eq!(graph.asymmetric_edges(), vec![(0, 1), (1, 0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn undirected_from_asymmetric_file() {
+        Graph::<char>::read_file_undirected("src/files/TestGraphData1.txt", false);
+    }
+
+    #[test]
+    fn try_undirected_from_asymmetric_file() {
+        let err = Graph::<char>::try_read_file_undirected("src/files/TestGraphData1.txt", false).unwrap_err();
+
+        assert!(matches!(&err.cause, GraphParseErrorKind::AsymmetricEdges(edges) if edges.len() == 6));
+
+        let graph: Graph<char> = Graph::try_read_file_undirected("src/files/TestGraphData2.txt", false).unwrap();
+
+        assert!(!graph.directed);
+    }
+
+    /*
+    Removal
+     */
+    #[test]
+    fn remove_edge() {
+        let mut graph = gen_graph();
+
+        assert_eq!(graph.remove_edge(1, 2).map(|c| c.idx), Some(2));
+        assert_eq!(graph.remove_edge(1, 2).map(|c| c.idx), None);
+        assert_eq!(graph.nodes[1].children.iter().map(|c| c.idx).collect::<Vec<usize>>(), vec![0, 3]);
+
+        // The edge back is only removed in undirected mode
+        assert_eq!(graph.nodes[2].children[0].idx, 1);
+
+        let mut graph: Graph<char> = Graph::new_undirected();
+        graph.add_nodes(vec![Node::new('a'), Node::new('b')]);
+        graph.add_edge(0, 1);
+        graph.remove_edge(1, 0);
+
+        assert!(graph.nodes.iter().all(|node| node.children.is_empty()));
+    }
+
+    #[test]
+    fn remove_node() {
+        let mut graph = gen_graph();
+        let mapping = graph.remove_node(2);
+
+        assert_eq!(mapping, vec![Some(0), Some(1), None, Some(2), Some(3), Some(4), Some(5)]);
+        assert_eq!(graph.nodes.iter().map(|n| n.val).collect::<Vec<u8>>(), vec![0, 1, 3, 4, 5, 6]);
+        assert_eq!(edges(&graph).iter().map(|(from, to, _)| (*from, *to)).collect::<Vec<(usize, usize)>>(), vec![
+            (0, 1), (1, 0), (1, 2), (2, 1), (3, 5), (4, 5), (5, 3), (5, 4)
+        ]);
+
+        // 0 -> 1 -> 3 is all that is left of the old bfs from 0
+        assert_eq!(graph.bfs(0).iter().map(|n| n.val).collect::<Vec<u8>>(), vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn retain_nodes() {
+        let mut graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let mapping = graph.retain_nodes(|node| node.val < 'F');
+
+        assert_eq!(mapping[..6], [Some(0), Some(1), Some(2), Some(3), Some(4), None]);
+        assert!(mapping[5..].iter().all(|new| new.is_none()));
+        assert_eq!(graph.nodes.len(), 5);
+        assert!(edges(&graph).iter().all(|(from, to, _)| *from < 5 && *to < 5));
+
+        // Nothing is removed, so the ids made before are still good
+        let id = graph.node_id(4).unwrap();
+
+        assert_eq!(graph.retain_nodes(|_| true), vec![Some(0), Some(1), Some(2), Some(3), Some(4)]);
+        assert_eq!(graph.resolve(id), Ok(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Node 7 does not exist")]
+    fn remove_node_out_of_range() {
+        let mut graph = gen_graph();
+        graph.remove_node(7);
+    }
+
+    /*
+    Node ids
+     */
+    #[test]
+    fn node_ids() {
+        let mut graph: Graph<char> = Graph::new();
+        let a = graph.add_node(Node::new('a'));
+        let b = graph.add_node(Node::new('b'));
+        let c = graph.add_node(Node::new('c'));
+
+        graph.add_edge_between(a, c, 0).unwrap();
+        graph.get_mut(b).unwrap().val = 'B';
+
+        assert_eq!(graph.resolve(c), Ok(2));
+        assert_eq!(graph[b].val, 'B');
+        assert_eq!(graph.ids().collect::<Vec<NodeId>>(), vec![a, b, c]);
+        assert_eq!(graph.node_id(2), Some(c));
+        assert_eq!(graph.node_id(3), None);
+
+        // Ids from another graph does not work, even with the same index
+        let other: Graph<char> = Graph::read_file("src/files/graf_13.txt", false);
+
+        assert_eq!(graph.resolve(other.node_id(0).unwrap()), Err(NodeIdError::Foreign));
+        assert!(graph.add_edge_between(a, other.node_id(1).unwrap(), 0).is_err());
+
+        // Removing a node makes the old ids stale
+        graph.remove_node(graph.resolve(b).unwrap());
+        let c = graph.node_id(1).unwrap();
+
+        assert_eq!(graph.get(a).map(|n| n.val), Err(NodeIdError::Stale { generation: 0, current: 1 }));
+        assert_eq!(graph[c].val, 'c');
+        assert_eq!(graph[graph.node_id(0).unwrap()].children[0].idx, 1);
+    }
+
+    #[test]
+    fn node_id_algorithms() {
+        let mut graph: Graph<char> = Graph::new();
+        let a = graph.add_node(Node::new('a'));
+        let b = graph.add_node(Node::new('b'));
+        let c = graph.add_node(Node::new('c'));
+        let d = graph.add_node(Node::new('d'));
+        graph.weighted = true;
+
+        graph.add_edge_between(a, b, 1).unwrap();
+        graph.add_edge_between(b, c, 2).unwrap();
+        graph.add_edge_between(a, c, 5).unwrap();
+
+        assert_eq!(graph.dfs_by_id(a).unwrap().iter().map(|n| n.val).collect::<Vec<char>>(), vec!['a', 'b', 'c']);
+        assert_eq!(graph.bfs_iter_by_id(b).unwrap().map(|(id, _)| id).collect::<Vec<NodeId>>(), vec![b, c]);
+        assert_eq!(graph.dfs_iter_by_id(a).unwrap().map(|(id, _)| id).collect::<Vec<NodeId>>(), vec![a, b, c]);
+        assert_eq!(graph.edges_between_by_id(a, c).unwrap()[0].weight, 5);
+
+        let forest = graph.bfs_from_many_by_id(&[b, d]).unwrap();
+
+        assert_eq!(forest.trees(), vec![vec![b, c], vec![d]]);
+        assert_eq!(forest.depth(c), Ok(Some(1)));
+        assert_eq!(forest.tree_of(a), Ok(None));
+        assert_eq!(graph.dfs_forest_by_id().trees(), vec![vec![a, b, c], vec![d]]);
+
+        let paths = graph.dijkstra_by_id(a).unwrap();
+
+        assert_eq!(paths.distance_to(c), Ok(Some(3)));
+        assert_eq!(paths.path_to(c), Ok(Some(vec![a, b, c])));
+        assert_eq!(paths.unreachable(), vec![d]);
+        assert_eq!(graph.bellman_ford_by_id(a).unwrap().path_to(d), Ok(None));
+
+        let astar = graph.astar_by_id(a, c, |_| 0).unwrap();
+
+        assert_eq!((astar.path(), astar.cost()), (vec![a, b, c], 3));
+        assert_eq!(graph.prim_by_id(a).unwrap().1, 3);
+
+        let max_flow = graph.max_flow_by_id(a, c).unwrap();
+
+        assert_eq!(max_flow.value(), 6);
+        assert_eq!(max_flow.cut(), vec![(a, b), (a, c)]);
+        assert_eq!(max_flow.is_source_side(b), Ok(false));
+
+        assert_eq!(graph.topological_sort_by_id(), Ok(vec![a, d, b, c]));
+        assert_eq!(graph.strongly_connected_components_by_id().len(), 4);
+
+        // Ids from another graph are errors everywhere, not a node at the same index
+        let other: Graph<char> = Graph::read_file("src/files/graf_13.txt", false);
+        let foreign = other.node_id(0).unwrap();
+
+        assert!(graph.dfs_by_id(foreign).is_err());
+        assert!(graph.bfs_from_many_by_id(&[a, foreign]).is_err());
+        assert_eq!(graph.dijkstra_by_id(foreign).unwrap_err(), ShortestPathError::InvalidNode(NodeIdError::Foreign));
+        assert_eq!(paths.path_to(foreign), Err(NodeIdError::Foreign));
+        assert_eq!(forest.depth(foreign), Err(NodeIdError::Foreign));
+        assert!(graph.astar_by_id(a, foreign, |_| 0).is_err());
+        assert!(graph.max_flow_by_id(foreign, c).is_err());
+        assert_eq!(graph.remove_edge_by_id(foreign, b).unwrap_err(), NodeIdError::Foreign);
+
+        let cycle = other.topological_sort_by_id().unwrap_err();
+
+        assert!(cycle.nodes().iter().all(|id| other.resolve(*id).is_ok()));
+
+        assert_eq!(graph.remove_edge_by_id(a, c).unwrap().map(|c| c.weight), Some(5));
+
+        // The removal made every old id stale, also the ones for nodes that are still there. The mapping gives the new ids
+        let mapping = graph.remove_node_by_id(b).unwrap();
+        let new_c = mapping.get(c).unwrap().unwrap();
+
+        assert_eq!(mapping.get(b), Ok(None));
+        assert_eq!(graph[new_c].val, 'c');
+        assert_eq!(graph.bfs_by_id(a).unwrap_err(), NodeIdError::Stale { generation: 0, current: 1 });
+        assert_eq!(paths.path_to(c), Ok(Some(vec![a, b, c])));
+        assert!(graph.remove_node_by_id(c).is_err());
+        assert_eq!(graph.nodes.len(), 3);
+        assert_eq!(mapping.get(new_c), Err(NodeIdError::Stale { generation: 1, current: 0 }));
+
+        let mapping = graph.retain_nodes_by_id(|node| node.val != 'a');
+
+        assert_eq!(mapping.get(new_c).unwrap().map(|id| graph[id].val), Some('c'));
+    }
+
+    #[test]
+    fn float_weights() {
+        let text = "3\n0 a 2 1 0.5 2 1.25\n1 b 1 2 2.5\n2 c 1 0 0.5\n";
+        let graph: Graph<String, f64> = read_graph(text.as_bytes(), Some(true)).unwrap();
+
+        assert_eq!(graph.nodes[0].children.iter().map(|c| c.weight).collect::<Vec<f64>>(), vec![0.5, 1.25]);
+        assert!(graph.to_string().contains("1 0.5"));
+
+        let mut written: Vec<u8> = vec![];
+        graph.write_to(&mut written).unwrap();
+
+        let read_back: Graph<String, f64> = read_graph(written.as_slice(), Some(true)).unwrap();
+
+        assert_eq!(read_back.nodes[1].children[0].weight, 2.5);
+
+        let err = read_graph::<String, i64, NoLabel, _>(text.as_bytes(), Some(true)).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, Some(9)));
+
+        // Undirected checks compares the float weights
+        let mut graph: Graph<char, f64> = Graph::new_undirected();
+        graph.add_nodes(vec![Node::new('a'), Node::new('b')]);
+        graph.weighted = true;
+        graph.add_edge_with_weight(0, 1, 0.1);
+
+        assert_eq!(graph.validate_undirected(), Ok(()));
+
+        graph.nodes[1].children[0].weight = 0.2;
+
+        assert_eq!(graph.asymmetric_edges(), vec![(0, 1), (1, 0)]);
+    }
+
+    #[test]
+    fn u32_weights() {
+        let mut node: Node<char, u32> = Node::new('a');
+        node.add_children(vec![Child::new(0), Child::new_with_weight(1, 7)]);
+
+        let mut graph: Graph<char, u32> = Graph::new();
+        graph.add_nodes(vec![node, Node::from(('b', vec![(0, 3)]))]);
+        graph.weighted = true;
+
+        assert_eq!(graph.nodes[0].children[0].weight, 0);
+        assert_eq!(graph.edge_cost(&graph.nodes[1].children[0]), 3);
+
+        let graph: Graph<char, u32> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let csr = CsrGraph::from(&graph);
+
+        assert_eq!(csr.weights(0), &[1, 2, 4]);
+    }
+
+    #[test]
+    fn float_weight_algorithms() {
+        let mut graph: Graph<char, f64> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from(('a', vec![(1, 0.5), (2, 2.0)])),
+            Node::from(('b', vec![(2, 0.25), (3, 3.0)])),
+            Node::from(('c', vec![(3, 1.5)])),
+            Node::new('d')
+        ]);
+        graph.weighted = true;
+
+        assert_eq!(graph.dijkstra(0).unwrap().distance_to(3), Some(2.25));
+        assert_eq!(graph.bellman_ford(0).unwrap().distance_to(2), Some(0.75));
+        assert_eq!(graph.floyd_warshall().unwrap().distance(1, 3), Some(1.75));
+
+        let astar = graph.astar(0, 3, |_| 0.0).unwrap();
+
+        assert_eq!((astar.path, astar.cost), (vec![0, 1, 2, 3], 2.25));
+
+        assert_eq!(graph.topological_sort(), Ok(vec![0, 1, 2, 3]));
+        assert_eq!(graph.strongly_connected_components(), vec![0, 1, 2, 3]);
+
+        let (tree, total) = graph.prim(0);
+
+        assert_eq!(total, 2.25);
+        assert_eq!(graph.kruskal().1, 2.25);
+        assert_eq!(tree.bridges().len(), 3);
+
+        assert_eq!(graph.max_flow(0, 3).value, 2.0);
+
+        let mut written: Vec<u8> = vec![];
+        graph.write_matrix_market(&mut written).unwrap();
+
+        assert!(String::from_utf8(written).unwrap().starts_with("%%MatrixMarket matrix coordinate real general\n"));
+    }
+
+    /*
+    Labels and parallel edges
+     */
+    #[test]
+    fn labeled_edges() {
+        let graph: Graph<String, i64, String> = Graph::read_file("src/files/TestGraphDataL1.txt", true);
+
+        let between: Vec<(i64, Option<&str>)> = graph.edges_between(0, 1).iter()
+            .map(|c| (c.weight, c.label.as_deref()))
+            .collect();
+
+        assert_eq!(between, vec![(5, Some("road")), (3, Some("rail"))]);
+        assert!(graph.edges_between(1, 0).is_empty());
+
+        let rail: Vec<(usize, usize)> = graph.edges_with_label(&"rail".to_string()).iter()
+            .map(|(from, c)| (*from, c.idx))
+            .collect();
+
+        assert_eq!(rail, vec![(0, 1), (2, 0)]);
+
+        // The cheapest of the parallel edges is used
+        assert_eq!(graph.dijkstra(0).unwrap().distance_to(2), Some(7));
+        assert!(graph.to_string().contains("1:rail 3"));
+
+        let mut written: Vec<u8> = vec![];
+        graph.write_to(&mut written).unwrap();
+
+        let read_back: Graph<String, i64, String> = read_graph(written.as_slice(), Some(true)).unwrap();
+
+        assert!(String::from_utf8(written).unwrap().contains("1:road 5"));
+        assert_eq!(read_back.edges_with_label(&"road".to_string()).len(), 2);
+
+        // Graphs without labels can not read labels
+        let err = Graph::<String>::try_read_file("src/files/TestGraphDataL1.txt", true).unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, Some(21)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidToken { expected: "a label", .. }));
+    }
+
+    #[test]
+    fn labeled_edges_undirected() {
+        let mut graph: Graph<char, i64, &str> = Graph::new_undirected();
+        graph.add_nodes(vec![Node::new('a'), Node::new('b')]);
+        graph.add_labeled_edge(0, 1, 2, "road");
+        graph.add_labeled_edge(0, 1, 2, "rail");
+        graph.add_edge(0, 1);
+
+        assert_eq!(graph.edges_between(0, 1).len(), 3);
+        assert_eq!(graph.edges_between(1, 0).iter().map(|c| c.label).collect::<Vec<Option<&str>>>(),
+                   vec![Some("road"), Some("rail"), None]);
+        assert_eq!(graph.edges_with_label(&"rail").len(), 2);
+
+        graph.remove_edge(0, 1);
+
+        assert_eq!(graph.edges_between(1, 0).len(), 2);
+
+        // The parallel edges are listed in a different order on the other side, the labels decide which edge goes back
+        let mut a: Node<char, i64, &str> = Node::new('a');
+        a.add_children(vec![Child::new_with_label(1, 2, "road"), Child::new_with_label(1, 2, "rail")]);
+        let mut b: Node<char, i64, &str> = Node::new('b');
+        b.add_children(vec![Child::new_with_label(0, 2, "rail"), Child::new_with_label(0, 2, "road")]);
+
+        let mut graph: Graph<char, i64, &str> = Graph::new_undirected();
+        graph.add_nodes(vec![a, b]);
+        graph.weighted = true;
+
+        assert_eq!(graph.validate_undirected(), Ok(()));
+        assert_eq!(graph.remove_edge(0, 1).and_then(|c| c.label), Some("road"));
+        assert_eq!(graph.edges_between(0, 1).iter().map(|c| c.label).collect::<Vec<Option<&str>>>(), vec![Some("rail")]);
+        assert_eq!(graph.edges_between(1, 0).iter().map(|c| c.label).collect::<Vec<Option<&str>>>(), vec![Some("rail")]);
+        assert_eq!(graph.validate_undirected(), Ok(()));
+
+        graph.nodes[1].children[0].label = Some("road");
+
+        assert_eq!(graph.asymmetric_edges(), vec![(0, 1), (1, 0)]);
+
+        let mut graph: Graph<char, i64, &str> = Graph::new();
+        graph.add_nodes(vec![Node::new('a')]);
+        graph.add_labeled_edge(0, 0, 1, "two words");
+
+        assert!(graph.write_to(Vec::new()).is_err());
+    }
+
+    /*
+    Compressed sparse row
+     */
+    #[test]
+    fn csr_graph() {
+        let graph = gen_graph();
+        let csr = CsrGraph::from(&graph);
+
+        assert_eq!(csr.num_nodes(), 7);
+        assert_eq!(csr.num_edges(), 14);
+        assert_eq!(csr.children(2), &[1, 3, 4]);
+        assert!(csr.has_edge(6, 5));
+        assert!(!csr.has_edge(5, 4));
+        assert_eq!(csr.get_neighborhood_matrix(), graph.get_neighborhood_matrix());
+
+        for from in 0..graph.nodes.len() {
+            assert_eq!(csr.dfs(from), graph.dfs(from).iter().map(|n| &n.val).collect::<Vec<&u8>>());
+            assert_eq!(csr.bfs(from), graph.bfs(from).iter().map(|n| &n.val).collect::<Vec<&u8>>());
+        }
+
+        let graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let dfs: Vec<char> = graph.dfs(7).iter().map(|n| n.val).collect();
+        let bfs: Vec<char> = graph.bfs(7).iter().map(|n| n.val).collect();
+        let csr = CsrGraph::from(graph);
+
+        assert!(csr.weighted);
+        assert_eq!(csr.weights(11), &[5, 1]);
+        assert_eq!(csr.dfs(7).into_iter().copied().collect::<Vec<char>>(), dfs);
+        assert_eq!(csr.bfs(7).into_iter().copied().collect::<Vec<char>>(), bfs);
+    }
+
+    // Random graph with about edges_per_node edges out of every node
+    fn gen_large_graph(num_nodes: usize, edges_per_node: usize) -> Graph<usize> {
+        let mut graph: Graph<usize> = Graph::new_with_size(num_nodes);
+        let mut seed: u64 = 12345;
+
+        for from in 0..num_nodes {
+            graph.nodes[from].val = from;
+
+            for _ in 0..edges_per_node {
+                seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                graph.add_edge(from, (seed >> 33) as usize % num_nodes);
+            }
+        }
+
+        graph
+    }
+
+    // cargo test --release csr_benchmark -- --ignored --nocapture
+    // The crate is only a binary, so there is no library a benches/ harness could link against
+    #[test]
+    #[ignore]
+    fn csr_benchmark() {
+        let graph = gen_large_graph(500_000, 8);
+        let csr = CsrGraph::from(&graph);
+
+        // One warm up run, then the fastest of the timed runs
+        let time = |name: &str, traversal: &dyn Fn() -> usize| {
+            let visited = traversal();
+
+            let best = (0..5)
+                .map(|_| {
+                    let start = Instant::now();
+                    black_box(traversal());
+                    start.elapsed()
+                })
+                .min()
+                .unwrap();
+
+            println!("{name:12} {visited} nodes in {best:?}");
+
+            best
+        };
+
+        let graph_dfs = time("Graph dfs", &|| graph.dfs(0).len());
+        let csr_dfs = time("CsrGraph dfs", &|| csr.dfs(0).len());
+        let graph_bfs = time("Graph bfs", &|| graph.bfs(0).len());
+        let csr_bfs = time("CsrGraph bfs", &|| csr.bfs(0).len());
+
+        println!("dfs speedup {:.1}x, bfs speedup {:.1}x",
+                 graph_dfs.as_secs_f64() / csr_dfs.as_secs_f64(),
+                 graph_bfs.as_secs_f64() / csr_bfs.as_secs_f64());
+
+        assert_eq!(csr.dfs(0).len(), graph.dfs(0).len());
+    }
+
+    /*
+    Articulation points and bridges
+     */
+    #[test]
+    fn articulation_points_and_bridges() {
+        let file = File::open("src/files/graf_13.txt").unwrap();
+        let mut graph: Graph<char> = Graph::try_from(file).unwrap();
+
+        assert_eq!(graph.articulation_points(), vec![0, 6, 7, 9]);
+        assert_eq!(graph.bridges(), vec![(0, 1), (6, 7), (7, 8), (9, 10)]);
+
+        // Without H -> G the nodes H and I are a separate part of the graph
+        graph.nodes[7].children.retain(|c| c.idx != 6);
+
+        assert_eq!(graph.articulation_points(), vec![0, 6, 9]);
+        assert_eq!(graph.bridges(), vec![(0, 1), (7, 8), (9, 10)]);
+    }
+
+    #[test]
+    fn articulation_points_and_bridges_undirected() {
+        let graph: Graph<char> = Graph::read_file_undirected("src/files/TestGraphData2.txt", false);
+
+        assert_eq!(graph.articulation_points(), vec![1, 2, 4, 6]);
+        assert_eq!(graph.bridges(), vec![(0, 1), (2, 4), (4, 6), (5, 6)]);
+
+        // Two edges between a and b, so only b - c is a bridge
+        let mut graph: Graph<char> = Graph::new_undirected();
+        graph.add_nodes(vec![Node::new('a'), Node::new('b'), Node::new('c')]);
+        graph.add_edge(0, 1);
+        graph.add_edge(0, 1);
+        graph.add_edge(1, 2);
+
+        assert_eq!(graph.articulation_points(), vec![1]);
+        assert_eq!(graph.bridges(), vec![(1, 2)]);
+    }
+
+    /*
+    Max flow
+     */
+    #[test]
+    fn max_flow() {
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphDataV5.txt", true);
+        let max_flow = graph.max_flow(0, 5);
+
+        assert_eq!(max_flow.value, 23);
+        assert_eq!(max_flow.source_side, vec![true, true, true, false, true, false]);
+        assert_eq!(max_flow.cut, vec![(1, 3), (4, 3), (4, 5)]);
+
+        // No edge has more flow than capacity, and what goes in to a node goes out again
+        let mut balance: Vec<i64> = vec![0; graph.nodes.len()];
+
+        for (idx, node) in graph.nodes.iter().enumerate() {
+            for (c, child) in node.children.iter().enumerate() {
+                let flow = max_flow.flow[idx][c];
+
+                assert!(flow >= 0 && flow <= child.weight);
+
+                balance[idx] -= flow;
+                balance[child.idx] += flow;
+            }
+        }
+
+        assert_eq!(balance, vec![-23, 0, 0, 0, 0, 23]);
+
+        // The graph is not changed
+        assert_eq!(graph.max_flow(0, 5).value, 23);
+    }
+
+    #[test]
+    fn max_flow_unsigned() {
+        let mut graph: Graph<char, u32> = Graph::new();
+        graph.add_nodes(vec![Node::from(('a', vec![(1, 3)])), Node::from(('b', vec![(2, 2)])), Node::new('c')]);
+        graph.weighted = true;
+
+        let max_flow = graph.max_flow(0, 2);
+
+        assert_eq!(max_flow.value, 2);
+        assert_eq!(max_flow.flow, vec![vec![2], vec![2], vec![]]);
+
+        // The same graph as in max_flow, with u32 capacities
+        let graph: Graph<char, u32> = Graph::read_file("src/files/TestGraphDataV5.txt", true);
+        let max_flow = graph.max_flow(0, 5);
+
+        assert_eq!(max_flow.value, 23);
+        assert_eq!(max_flow.cut, vec![(1, 3), (4, 3), (4, 5)]);
+    }
+
+    #[test]
+    fn max_flow_unweighted() {
+        // Every edge has capacity 1, so the flow is the number of edge disjoint paths
+        let graph = gen_graph();
+
+        assert_eq!(graph.max_flow(1, 6).value, 1);
+        assert_eq!(graph.max_flow(2, 1).value, 2);
+        assert_eq!(graph.max_flow(2, 2).value, 0);
+
+        let file = File::open("src/files/graf_13.txt").unwrap();
+        let graph: Graph<char> = Graph::try_from(file).unwrap();
+        let max_flow = graph.max_flow(7, 1);
+
+        assert_eq!(max_flow.value, 1);
+        assert_eq!(max_flow.cut, vec![(7, 6)]);
+
+        // B has no edges out
+        let max_flow = graph.max_flow(1, 0);
+
+        assert_eq!(max_flow.value, 0);
+        assert_eq!(max_flow.cut, vec![]);
+    }
+
+    /*
+    A*
+     */
+    fn gen_grid(size: usize) -> Graph<(usize, usize)> {
+        let mut graph: Graph<(usize, usize)> = Graph::new_undirected();
+
+        for y in 0..size {
+            for x in 0..size {
+                graph.add_node(Node::new((x, y)));
+            }
+        }
+
+        for y in 0..size {
+            for x in 0..size {
+                if x + 1 < size {
+                    graph.add_edge(y * size + x, y * size + x + 1);
+                }
+
+                if y + 1 < size {
+                    graph.add_edge(y * size + x, (y + 1) * size + x);
+                }
+            }
+        }
+
+        graph
+    }
+
+    #[test]
+    fn astar() {
+        let graph = gen_grid(10);
+        let (to_x, to_y) = (9, 0);
+
+        let manhattan = |node: &Node<(usize, usize)>| (node.val.0.abs_diff(to_x) + node.val.1.abs_diff(to_y)) as i64;
+
+        let astar = graph.astar(0, 9, manhattan).unwrap();
+        let without_heuristic = graph.astar(0, 9, |_| 0).unwrap();
+
+        assert_eq!(astar.path, (0..10).collect::<Vec<usize>>());
+        assert_eq!(astar.cost, 9);
+        assert_eq!(astar.expanded, 10);
+
+        // Same cost as dijkstra, but the heuristic finds it with fewer expanded nodes
+        assert_eq!(without_heuristic.cost, graph.dijkstra(0).unwrap().distance_to(9).unwrap());
+        assert!(astar.expanded < without_heuristic.expanded);
+    }
+
+    #[test]
+    fn astar_from_file() {
+        let graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let astar = graph.astar(0, 12, |_| 0).unwrap();
+
+        assert_eq!(astar.path, vec![0, 6, 9, 12]);
+        assert_eq!(astar.cost, 7);
+        assert_eq!(graph.astar(0, 7, |_| 0).unwrap_err(), ShortestPathError::Unreachable { from: 0, to: 7 });
+
+        let graph: Graph<char> = Graph::read_file("src/files/TestGraphDataV3.txt", true);
+
+        assert!(matches!(graph.astar(0, 4, |_| 0), Err(ShortestPathError::NegativeWeight { .. })));
+    }
+
+    #[test]
+    fn astar_inconsistent_heuristic() {
+        let mut graph: Graph<u8> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from((0, vec![(1, 1), (2, 4)])),
+            Node::from((1, vec![(2, 1)])),
+            Node::from((2, vec![(3, 10)])),
+            Node::new(3)
+        ]);
+        graph.weighted = true;
+
+        // Never too high, but 2 is done through 0 before the shorter way through 1 is found
+        let astar = graph.astar(0, 3, |node| if node.val == 1 { 11 } else { 0 }).unwrap();
+
+        assert_eq!(astar.path, vec![0, 1, 2, 3]);
+        assert_eq!(astar.cost, 12);
+        assert_eq!(Some(astar.cost), graph.dijkstra(0).unwrap().distance_to(3));
+
+        // A heuristic of i64::MAX saturates instead of wrapping around to a negative estimate, so the dead end 2 is never expanded
+        let mut graph: Graph<u8> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from((0, vec![(1, 1), (2, 1)])),
+            Node::from((1, vec![(3, 1)])),
+            Node::new(2),
+            Node::new(3)
+        ]);
+        graph.weighted = true;
+
+        let astar = graph.astar(0, 3, |node| if node.val == 2 { i64::MAX } else { 0 }).unwrap();
+
+        assert_eq!(astar.cost, 2);
+        assert_eq!(astar.expanded, 3);
+    }
+
+    /*
+    Fallible reading
+     */
+    #[test]
+    fn try_read_file() {
+        let graph: Graph<char> = Graph::try_read_file("src/files/vgraf_13.txt", true).unwrap();
+
+        assert!(graph.weighted);
+        assert_eq!(graph.dijkstra(0).unwrap().distance_to(12), Some(7));
+
+        let graph: Graph<String> = Graph::try_from(File::open("src/files/TestGraphDataV2.txt").unwrap()).unwrap();
+
+        assert!(graph.weighted);
+        assert_eq!(graph.nodes[3].val, "V3");
+
+        let err = Graph::<char>::try_read_file("src/files/does_not_exist.txt", false).unwrap_err();
+
+        assert_eq!(err.line, 0);
+        assert!(matches!(err.cause, GraphParseErrorKind::Io(_)));
+    }
+
+    #[test]
+    fn try_read_errors() {
+        let read = |text: &str| read_graph::<char, i64, NoLabel, _>(text.as_bytes(), None).unwrap_err();
+
+        let err = read("x\n");
+        assert_eq!((err.line, err.column), (1, Some(1)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidToken { expected: "the number of nodes", .. }));
+
+        let err = read("2\n0 a 1 1\n2 b 0\n");
+        assert_eq!((err.line, err.column), (3, Some(1)));
+        assert!(matches!(err.cause, GraphParseErrorKind::IndexOutOfRange { index: 2, num_nodes: 2 }));
+
+        let err = read("2\n0 a 2 1\n1 b 0\n");
+        assert_eq!((err.line, err.column), (2, None));
+        assert!(matches!(err.cause, GraphParseErrorKind::WrongChildCount { expected: 2, found: 1 }));
+
+        let err = read("2\n0 a 1   5\n1 b 0\n");
+        assert_eq!((err.line, err.column), (2, Some(9)));
+        assert!(matches!(err.cause, GraphParseErrorKind::DanglingChild { child: 5, num_nodes: 2 }));
+
+        let err = read("2\n0 a 1 1 x\n1 b 0\n");
+        assert_eq!((err.line, err.column), (2, Some(9)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidToken { expected: "a weight", .. }));
+
+        let err = read("2\n0 ab 0\n");
+        assert_eq!((err.line, err.column), (2, Some(3)));
+        assert_eq!(err.to_string(), "line 2, column 3: could not parse (ab) as a node value");
+
+        let err = read("2\n0 a\n");
+        assert_eq!((err.line, err.column), (2, None));
+        assert!(matches!(err.cause, GraphParseErrorKind::MissingToken("the number of children")));
+
+        // Counts too large for the input are errors, not panics
+        let err = read("18446744073709551615\n");
+        assert_eq!((err.line, err.column), (1, Some(1)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidHeader(_)));
+
+        let err = read("1\n0 a 18446744073709551615 0\n");
+        assert!(matches!(err.cause, GraphParseErrorKind::WrongChildCount { expected: usize::MAX, found: 1 }));
+
+        let err = read_graph::<char, i64, NoLabel, _>("1\n0 a 18446744073709551615 0\n".as_bytes(), Some(true)).unwrap_err();
+        assert!(matches!(err.cause, GraphParseErrorKind::WrongChildCount { expected: usize::MAX, found: 1 }));
+
+        // The nodes are made from the lines, so a large count without the lines is an error instead of a huge allocation
+        let err = read("200000000\n");
+        assert_eq!((err.line, err.column), (1, Some(1)));
+        assert_eq!(err.to_string(), "line 1, column 1: invalid header, the header says 200000000 nodes, but there is no line for node 0");
+
+        let err = read("3\n2 c 0\n0 a 1 2\n");
+        assert_eq!(err.to_string(), "line 1, column 1: invalid header, the header says 3 nodes, but there is no line for node 1");
+
+        // The lines can come in any order
+        let graph: Graph<char> = read_graph("2\n1 b 0\n0 a 1 1\n".as_bytes(), None).unwrap();
+        assert_eq!(graph.nodes.iter().map(|n| n.val).collect::<Vec<char>>(), vec!['a', 'b']);
+        assert_eq!(graph.nodes[0].children[0].idx, 1);
+    }
+
+    /*
+    Generic reading
+     */
+    #[derive(Debug, Default, PartialEq)]
+    struct Station {
+        name: String
+    }
+
+    impl FromStr for Station {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.strip_prefix('V') {
+                Some(name) => Ok(Station { name: name.to_string() }),
+                None => Err(format!("({s}) is not a station"))
+            }
+        }
+    }
+
+    #[test]
+    fn read_file_generic() {
+        let graph: Graph<u32> = Graph::read_file("src/files/TestGraphData2.txt", false);
+
+        assert_eq!(graph.nodes.iter().map(|n| n.val).collect::<Vec<u32>>(), vec![0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(graph.bfs(2).iter().map(|n| n.val).collect::<Vec<u32>>(), vec![2, 1, 3, 4, 0, 6, 5]);
+
+        let graph: Graph<f64> = Graph::try_read_file_inferred("src/files/TestGraphDataV1.txt").unwrap();
+
+        assert!(graph.weighted);
+        assert_eq!(graph.nodes[4].val, 5.0);
+        assert_eq!(graph.nodes[4].children.iter().map(|c| (c.idx, c.weight)).collect::<Vec<(usize, i64)>>(), vec![(1, 4), (2, 34), (3, 58)]);
+
+        let graph: Graph<Station> = Graph::try_from(File::open("src/files/TestGraphDataV2.txt").unwrap()).unwrap();
+
+        assert!(graph.weighted);
+        assert_eq!(graph.nodes[6].val, Station { name: "6".to_string() });
+
+        // Node values that can not be parsed are reported
+        let err = Graph::<Station>::try_read_file_inferred("src/files/TestGraphData1.txt").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, Some(3)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidToken { expected: "a node value", .. }));
+    }
+
+    /*
+    Writing
+     */
+    // Value and (child, weight, label) for every node
+    type GraphContent = Vec<(String, Vec<(usize, i64, Option<String>)>)>;
+
+    fn graph_content(graph: &Graph<String, i64, String>) -> GraphContent {
+        graph.nodes.iter()
+            .map(|node| (
+                node.val.clone(),
+                node.children.iter().map(|c| (c.idx, if graph.weighted { c.weight } else { 0 }, c.label.clone())).collect()
+            ))
+            .collect()
+    }
+
+    #[test]
+    fn write_to_same_as_file() {
+        for file_name in ["src/files/graf_13.txt", "src/files/vgraf_13.txt"] {
+            let graph: Graph<char> = Graph::try_read_file_inferred(file_name).unwrap();
+            let mut written: Vec<u8> = vec![];
+
+            graph.write_to(&mut written).unwrap();
+
+            let file_content = fs::read_to_string(file_name).unwrap();
+
+            assert_eq!(String::from_utf8(written).unwrap(), file_content.trim_end().to_string() + "\n");
+        }
+    }
+
+    #[test]
+    fn write_to_round_trip() {
+        for entry in fs::read_dir("src/files").unwrap() {
+            let path = entry.unwrap().path();
+            // Labels are read as strings so the labeled files are included
+            let graph: Graph<String, i64, String> = Graph::try_read_file_inferred(path.to_str().unwrap()).unwrap();
+
+            let mut written: Vec<u8> = vec![];
+            graph.write_to(&mut written).unwrap();
+
+            let read_back: Graph<String, i64, String> = read_graph(written.as_slice(), None).unwrap();
+
+            assert_eq!(read_back.weighted, graph.weighted, "{path:?}");
+            assert_eq!(graph_content(&read_back), graph_content(&graph), "{path:?}");
+
+            // Writing it again gives the same text
+            let mut written_again: Vec<u8> = vec![];
+            read_back.write_to(&mut written_again).unwrap();
+
+            assert_eq!(written_again, written, "{path:?}");
+        }
+    }
+
+    #[test]
+    fn write_to_invalid_value() {
+        let mut graph: Graph<String> = Graph::new();
+        graph.add_node(Node::new("two words".to_string()));
+
+        let err = graph.write_to(vec![]).unwrap_err();
+
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    /*
+    DOT
+     */
+    #[test]
+    fn to_dot() {
+        let graph: Graph<String> = Graph::read_file("src/files/TestGraphDataV2.txt", true);
+        let path = graph.dijkstra(0).unwrap().path_to(5).unwrap();
+        let dot = graph.to_dot(&DotOptions::new().highlight_path(path));
+
+        assert!(dot.starts_with("digraph {\n    0 [label=\"V0\", color=red, penwidth=2];\n    1 [label=\"V1\"];\n"));
+        assert!(dot.contains("    0 -> 1 [label=\"2\"];\n"));
+        assert!(dot.contains("    0 -> 3 [label=\"1\", color=red, penwidth=2];\n"));
+        assert!(dot.contains("    3 -> 6 [label=\"4\", color=red, penwidth=2];\n"));
+        assert!(dot.ends_with("    6 -> 5 [label=\"1\", color=red, penwidth=2];\n}\n"));
+    }
+
+    #[test]
+    fn to_dot_highlight_nodes() {
+        let graph: Graph<char> = Graph::read_file_undirected("src/files/TestGraphData2.txt", false);
+        let visited: Vec<usize> = graph.dfs(5)
+            .iter()
+            .take(3)
+            .map(|node| graph.index_of(node).unwrap())
+            .collect();
+
+        let dot = graph.to_dot(&DotOptions::new().highlight_nodes(visited));
+
+        assert_eq!(dot, "graph {
+    0 [label=\"0\"];
+    1 [label=\"1\"];
+    2 [label=\"2\"];
+    3 [label=\"3\"];
+    4 [label=\"4\", style=filled, fillcolor=lightblue];
+    5 [label=\"5\", style=filled, fillcolor=lightblue];
+    6 [label=\"6\", style=filled, fillcolor=lightblue];
+    0 -- 1;
+    1 -- 2;
+    1 -- 3;
+    2 -- 3;
+    2 -- 4;
+    4 -- 6;
+    5 -- 6;
+}
+");
+
+        let mut graph: Graph<String> = Graph::new();
+        graph.add_node(Node::new("say \"hi\"".to_string()));
+
+        assert!(graph.to_dot(&DotOptions::new()).contains("label=\"say \\\"hi\\\"\""));
+    }
+
+    /*
+    Edge list, DIMACS and Matrix Market
+     */
+    fn edges<T>(graph: &Graph<T>) -> Vec<(usize, usize, i64)> {
+        graph.nodes.iter()
+            .enumerate()
+            .flat_map(|(idx, node)| node.children.iter().map(move |c| (idx, c.idx, c.weight)))
+            .collect()
+    }
+
+    #[test]
+    fn edge_list() {
+        let graph = read_edge_list("# from to weight\n0 1 4\n\n1 3 -2\n3 0 1\n".as_bytes()).unwrap();
+
+        assert!(graph.weighted);
+        assert_eq!(graph.nodes.iter().map(|n| n.val).collect::<Vec<usize>>(), vec![0, 1, 2, 3]);
+        assert_eq!(edges(&graph), vec![(0, 1, 4), (1, 3, -2), (3, 0, 1)]);
+
+        let original: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let mut written: Vec<u8> = vec![];
+        original.write_edge_list(&mut written).unwrap();
+
+        assert!(String::from_utf8(written.clone()).unwrap().starts_with("0 1 1\n0 5 2\n0 6 4\n2 0 1\n"));
+        assert_eq!(edges(&read_edge_list(written.as_slice()).unwrap()), edges(&original));
+
+        let err = read_edge_list::<i64>("0 1 2\n1 2\n".as_bytes()).unwrap_err();
+        assert_eq!(err.line, 2);
+        assert!(matches!(err.cause, GraphParseErrorKind::MixedWeights));
+
+        let err = read_edge_list::<i64>("0 -1\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (1, Some(3)));
+
+        let err = read_edge_list::<i64>("0 1 2 3\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (1, Some(7)));
+
+        // Indexes too large to make room for are errors, not panics or huge allocations
+        let err = read_edge_list::<i64>("0 1\n0 18446744073709551615\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, Some(3)));
+        assert!(matches!(err.cause, GraphParseErrorKind::IndexOutOfRange { index: usize::MAX, num_nodes: 2 }));
+
+        let err = read_edge_list::<i64>("4000000000 0\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (1, Some(1)));
+        assert!(matches!(err.cause, GraphParseErrorKind::IndexOutOfRange { index: 4000000000, num_nodes: 1 }));
+
+        // A file can not have more nodes than characters, even when the index fits in memory
+        let err = read_edge_list::<i64>("0 1\n0 200000000\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, Some(3)));
+        assert!(matches!(err.cause, GraphParseErrorKind::IndexOutOfRange { index: 200000000, num_nodes: 2 }));
+
+        assert_eq!(read_edge_list::<i64>("0 9\n".as_bytes()).unwrap_err().line, 1);
+        assert_eq!(read_edge_list::<i64>("0 3\n".as_bytes()).unwrap().nodes.len(), 4);
+    }
+
+    #[test]
+    fn dimacs() {
+        let graph = read_dimacs("c small graph\np sp 3 3\na 1 2 5\na 2 3 1\nc more\na 3 1 2\n".as_bytes()).unwrap();
+
+        assert!(graph.weighted);
+        assert_eq!(edges(&graph), vec![(0, 1, 5), (1, 2, 1), (2, 0, 2)]);
+
+        let original: Graph<String> = Graph::read_file("src/files/TestGraphDataV2.txt", true);
+        let mut written: Vec<u8> = vec![];
+        original.write_dimacs(&mut written).unwrap();
+
+        assert!(String::from_utf8(written.clone()).unwrap().starts_with("p sp 7 12\na 1 2 2\n"));
+
+        let read_back = read_dimacs(written.as_slice()).unwrap();
+
+        assert_eq!(edges(&read_back), edges(&original));
+        assert_eq!(read_back.dijkstra(0).unwrap().distance, original.dijkstra(0).unwrap().distance);
+
+        let err = read_dimacs::<i64>("a 1 2 3\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidHeader(_)));
+
+        let err = read_dimacs::<i64>("p sp 2 1\na 1 3 1\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, Some(5)));
+        assert!(matches!(err.cause, GraphParseErrorKind::IndexOutOfRange { index: 3, num_nodes: 2 }));
+
+        let err = read_dimacs::<i64>("p sp 2 2\na 1 2 1\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.cause, GraphParseErrorKind::WrongEdgeCount { expected: 2, found: 1 }));
+
+        let err = read_dimacs::<i64>("p sp 18446744073709551615 0\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (1, Some(6)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidHeader(_)));
+
+        let err = read_dimacs::<i64>("c nodes without arcs\np sp 200000000 0\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, Some(6)));
+        assert_eq!(err.to_string(), "line 2, column 6: invalid header, 200000000 nodes is more than a file of 38 characters can describe");
+
+        assert_eq!(read_dimacs::<i64>("p sp 5 0\n".as_bytes()).unwrap().nodes.len(), 5);
+    }
+
+    #[test]
+    fn formats_round_trip() {
+        let mut graph: Graph<char> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from(('a', vec![(1, -3)])),
+            Node::from(('b', vec![(2, 7), (0, 1)])),
+            Node::from(('c', vec![(2, 0)]))
+        ]);
+        graph.weighted = true;
+
+        let mut edge_list: Vec<u8> = vec![];
+        let mut dimacs: Vec<u8> = vec![];
+        let mut matrix_market: Vec<u8> = vec![];
+        graph.write_edge_list(&mut edge_list).unwrap();
+        graph.write_dimacs(&mut dimacs).unwrap();
+        graph.write_matrix_market(&mut matrix_market).unwrap();
+
+        assert_eq!(edges(&read_edge_list(edge_list.as_slice()).unwrap()), edges(&graph));
+        assert_eq!(edges(&read_dimacs(dimacs.as_slice()).unwrap()), edges(&graph));
+        assert_eq!(edges(&read_matrix_market(matrix_market.as_slice()).unwrap()), edges(&graph));
+
+        let mut graph: Graph<char, f64> = Graph::new();
+        graph.add_nodes(vec![
+            Node::from(('a', vec![(1, 0.5)])),
+            Node::from(('b', vec![(2, -1.25), (0, 2.0)])),
+            Node::from(('c', vec![(2, 1e-3)]))
+        ]);
+        graph.weighted = true;
+
+        let float_edges = |graph: &Graph<usize, f64>| graph.nodes.iter()
+            .enumerate()
+            .flat_map(|(idx, node)| node.children.iter().map(move |c| (idx, c.idx, c.weight)))
+            .collect::<Vec<(usize, usize, f64)>>();
+        let expected = vec![(0, 1, 0.5), (1, 2, -1.25), (1, 0, 2.0), (2, 2, 1e-3)];
+
+        let mut edge_list: Vec<u8> = vec![];
+        let mut dimacs: Vec<u8> = vec![];
+        let mut matrix_market: Vec<u8> = vec![];
+        graph.write_edge_list(&mut edge_list).unwrap();
+        graph.write_dimacs(&mut dimacs).unwrap();
+        graph.write_matrix_market(&mut matrix_market).unwrap();
+
+        assert_eq!(float_edges(&read_edge_list(edge_list.as_slice()).unwrap()), expected);
+        assert_eq!(float_edges(&read_dimacs(dimacs.as_slice()).unwrap()), expected);
+        assert_eq!(float_edges(&read_matrix_market(matrix_market.as_slice()).unwrap()), expected);
+
+        // Integer weights still need whole numbers
+        let err = read_matrix_market::<i64>(matrix_market.as_slice()).unwrap_err();
+        assert_eq!((err.line, err.column), (3, Some(5)));
+    }
+
+    #[test]
+    fn matrix_market() {
+        let text = "%%MatrixMarket matrix coordinate real symmetric
+% comment
+3 3 3
+2 1 4.0
+3 2 1
+3 3 2
+";
+        let graph = read_matrix_market(text.as_bytes()).unwrap();
+
+        assert!(graph.weighted);
+        assert!(!graph.directed);
+        assert_eq!(edges(&graph), vec![(0, 1, 4), (1, 0, 4), (1, 2, 1), (2, 1, 1), (2, 2, 2)]);
+
+        let mut written: Vec<u8> = vec![];
+        graph.write_matrix_market(&mut written).unwrap();
+
+        assert_eq!(String::from_utf8(written).unwrap(), "%%MatrixMarket matrix coordinate integer symmetric
+3 3 3
+2 1 4
+3 2 1
+3 3 2
+");
+
+        let original: Graph<char> = Graph::read_file("src/files/graf_13.txt", false);
+        let mut written: Vec<u8> = vec![];
+        original.write_matrix_market(&mut written).unwrap();
+
+        let read_back = read_matrix_market(written.as_slice()).unwrap();
+
+        assert!(!read_back.weighted);
+        assert!(read_back.directed);
+        assert_eq!(edges(&read_back), edges(&original));
+
+        let err = read_matrix_market::<i64>("%%MatrixMarket matrix array real general\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidHeader(_)));
+
+        let err = read_matrix_market::<i64>("%%MatrixMarket matrix coordinate real general\n2 3 0\n".as_bytes()).unwrap_err();
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidHeader(_)));
+
+        let err = read_matrix_market::<i64>("%%MatrixMarket matrix coordinate real general\n2 2 1\n1 2 0.5\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (3, Some(5)));
+
+        let err = read_matrix_market::<i64>("%%MatrixMarket matrix coordinate real general\n18446744073709551615 18446744073709551615 0\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, Some(1)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidHeader(_)));
+
+        let err = read_matrix_market::<i64>("%%MatrixMarket matrix coordinate real general\n200000000 200000000 0\n".as_bytes()).unwrap_err();
+        assert_eq!((err.line, err.column), (2, Some(1)));
+        assert!(matches!(err.cause, GraphParseErrorKind::InvalidHeader(_)));
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use crate::array::avl_tree::AVLTree;
+    use crate::array::binary_tree::BinaryTree;
+    use crate::array::graph::{Child, Graph};
+    use crate::utils::read_file::FileReader;
+    use crate::utils::util::{Insert, OrderedTraversal};
+
+    /*
+    Graph
+     */
+    #[test]
+    fn graph_round_trip() {
+        let graph: Graph<char> = Graph::read_file("src/files/vgraf_13.txt", true);
+        let text = ron::to_string(&graph).unwrap();
+
+        assert!(text.starts_with("(nodes:[(val:'A',children:[(idx:1,weight:1),"));
+
+        let read_back: Graph<char> = ron::from_str(&text).unwrap();
+
+        assert!(read_back.weighted);
+        assert!(read_back.directed);
+        assert_eq!(read_back.nodes.len(), 13);
+        assert_eq!(read_back.dijkstra(0).unwrap().distance, graph.dijkstra(0).unwrap().distance);
+    }
+
+    #[test]
+    fn graph_invalid() {
+        let dangling = r#"(nodes:[(val:"a",children:[(idx:1,weight:0)])],weighted:false,directed:true)"#;
+        let err = ron::from_str::<Graph<String>>(dangling).unwrap_err();
+
+        assert!(err.to_string().contains("Node 0 has the child 1, but there are only 1 nodes"));
+
+        // Undirected, but the edge back is missing
+        let asymmetric = r#"(nodes:[
+            (val:"a",children:[(idx:1,weight:0)]),
+            (val:"b",children:[])
+        ],weighted:false,directed:false)"#;
+
+        assert!(ron::from_str::<Graph<String>>(asymmetric).is_err());
+
+        let child: Child = ron::from_str("(idx:3,weight:-2)").unwrap();
+
+        assert_eq!((child.idx, child.weight), (3, -2));
+    }
+
+    /*
+    Trees
+     */
+    #[test]
+    fn binary_tree_round_trip() {
+        let mut tree: BinaryTree<u8> = BinaryTree::new();
+        tree.insert_vec(vec![8, 3, 1, 6, 4, 7, 10, 14, 13]);
+
+        let text = ron::to_string(&tree).unwrap();
+        let read_back: BinaryTree<u8> = ron::from_str(&text).unwrap();
+
+        assert_eq!(read_back.root, tree.root);
+        assert_eq!(read_back.in_order(), vec![1, 3, 4, 6, 7, 8, 10, 13, 14]);
+    }
+
+    #[test]
+    fn binary_tree_invalid() {
+        // Not 2^levels - 1 elements
+        assert!(ron::from_str::<BinaryTree<u8>>("(root:[Some(2),Some(1)],nodes:2,height:1)").is_err());
+
+        // nodes and height does not match the array
+        assert!(ron::from_str::<BinaryTree<u8>>("(root:[Some(2),Some(1),Some(3)],nodes:7,height:1)").is_err());
+        assert!(ron::from_str::<BinaryTree<u8>>("(root:[Some(2),Some(1),Some(3)],nodes:3,height:5)").is_err());
+
+        // Value without a parent
+        assert!(ron::from_str::<BinaryTree<u8>>("(root:[Some(2),None,Some(3)],nodes:3,height:1)").is_ok());
+        assert!(ron::from_str::<BinaryTree<u8>>("(root:[Some(2),None,Some(3),None,None,Some(4),None],nodes:7,height:2)").is_err());
+
+        // 4 is on the left side of 2, and 6 is on the right side of 2 but left of 5
+        assert!(ron::from_str::<BinaryTree<u8>>("(root:[Some(2),Some(4),Some(3)],nodes:3,height:1)").is_err());
+        assert!(ron::from_str::<BinaryTree<u8>>("(root:[Some(5),Some(2),Some(8),None,Some(6),None,None],nodes:7,height:2)").is_err());
+    }
+
+    #[test]
+    fn avl_tree_round_trip() {
+        let tree: AVLTree<char> = AVLTree::new_from_vec(vec!['a', 'b', 'c', 'd']);
+
+        let text = ron::to_string(&tree).unwrap();
+        let read_back: AVLTree<char> = ron::from_str(&text).unwrap();
+
+        assert_eq!(read_back.root, tree.root);
+        assert_eq!(read_back.balance_factor, tree.balance_factor);
+
+        // The balance factors has to match the array
+        assert!(ron::from_str::<AVLTree<char>>("(root:[Some('b'),Some('a'),Some('c')],balance_factor:[2,1],nodes:3,height:1)").is_err());
+        assert!(ron::from_str::<AVLTree<char>>("(root:[Some('b'),Some('a'),None],balance_factor:[2,1,1],nodes:3,height:1)").is_err());
+    }
+}
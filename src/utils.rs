@@ -1,2 +1,9 @@
 pub mod util;
-pub mod read_file;
\ No newline at end of file
+pub mod read_file;
+pub mod write_file;
+pub mod dot;
+pub mod formats;
+pub mod bit_matrix;
+pub mod union_find;
+#[cfg(feature = "serde")]
+pub mod serialize;
\ No newline at end of file
@@ -0,0 +1,98 @@
+/*
+BitMatrix
+
+Square matrix of bools packed 64 to a word, every row starts on a new word.
+ */
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitMatrix {
+    pub(crate) size: usize,
+    pub(crate) words_per_row: usize,
+    pub(crate) words: Vec<u64>
+}
+
+impl BitMatrix {
+    pub(crate) fn new(size: usize) -> BitMatrix {
+        let words_per_row = size.div_ceil(WORD_BITS);
+
+        BitMatrix {
+            size,
+            words_per_row,
+            words: vec![0; size * words_per_row]
+        }
+    }
+
+    pub(crate) fn identity(size: usize) -> BitMatrix {
+        let mut matrix = BitMatrix::new(size);
+
+        (0..size).for_each(|i| matrix.set(i, i, true));
+
+        matrix
+    }
+
+    pub(crate) fn get(&self, row: usize, column: usize) -> bool {
+        assert!(column < self.size, "column {column} is out of range for a {0}x{0} matrix", self.size);
+
+        self.words[row * self.words_per_row + column / WORD_BITS] & (1 << (column % WORD_BITS)) != 0
+    }
+
+    pub(crate) fn set(&mut self, row: usize, column: usize, val: bool) {
+        assert!(column < self.size, "column {column} is out of range for a {0}x{0} matrix", self.size);
+
+        let word = &mut self.words[row * self.words_per_row + column / WORD_BITS];
+        let bit = 1 << (column % WORD_BITS);
+
+        if val {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    pub(crate) fn row(&self, row: usize) -> &[u64] {
+        &self.words[row * self.words_per_row..(row + 1) * self.words_per_row]
+    }
+
+    // row to = row to | row from, a whole word at a time
+    pub(crate) fn or_row(&mut self, to: usize, from: usize) {
+        if to == from {
+            return;
+        }
+
+        for word in 0..self.words_per_row {
+            self.words[to * self.words_per_row + word] |= self.words[from * self.words_per_row + word];
+        }
+    }
+
+    // Number of true cells in the row
+    pub(crate) fn count_row(&self, row: usize) -> usize {
+        self.row(row).iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub(crate) fn to_vec(&self) -> Vec<Vec<bool>> {
+        (0..self.size)
+            .map(|row| (0..self.size).map(|column| self.get(row, column)).collect())
+            .collect()
+    }
+}
+
+impl From<BitMatrix> for Vec<Vec<bool>> {
+    fn from(value: BitMatrix) -> Self {
+        value.to_vec()
+    }
+}
+
+impl From<&Vec<Vec<bool>>> for BitMatrix {
+    fn from(value: &Vec<Vec<bool>>) -> Self {
+        let mut matrix = BitMatrix::new(value.len());
+
+        for (row, cells) in value.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                matrix.set(row, column, *cell);
+            }
+        }
+
+        matrix
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use crate::array::graph::{Graph, Node};
+use crate::array::graph::weight::Weight;
+
+/*
+Graphviz DOT export
+ */
+#[derive(Debug, Default)]
+pub struct DotOptions {
+    pub(crate) highlight_nodes: Vec<usize>,  // Filled nodes, e.g. the result of dfs or bfs
+    pub(crate) highlight_path: Vec<usize>    // Nodes and the edges between them drawn in red, e.g. a shortest path
+}
+
+impl DotOptions {
+    pub(crate) fn new() -> DotOptions {
+        DotOptions::default()
+    }
+
+    pub(crate) fn highlight_nodes(mut self, nodes: Vec<usize>) -> DotOptions {
+        self.highlight_nodes = nodes;
+        self
+    }
+
+    pub(crate) fn highlight_path(mut self, path: Vec<usize>) -> DotOptions {
+        self.highlight_path = path;
+        self
+    }
+}
+
+impl<T, W, E> Graph<T, W, E> {
+    // Index of a node returned by dfs, bfs, etc.
+    pub(crate) fn index_of(&self, node: &Node<T, W, E>) -> Option<usize> {
+        self.nodes.iter().position(|n| std::ptr::eq(n, node))
+    }
+}
+
+impl<T, W, E> Graph<T, W, E>
+    where T: Display, W: Weight {
+    // Undirected graphs are written as a graph with every edge once
+    pub(crate) fn to_dot(&self, options: &DotOptions) -> String {
+        let highlight_nodes: HashSet<usize> = options.highlight_nodes.iter().copied().collect();
+        let path_nodes: HashSet<usize> = options.highlight_path.iter().copied().collect();
+        let path_edges: HashSet<(usize, usize)> = options.highlight_path
+            .windows(2)
+            .flat_map(|edge| if self.directed {
+                vec![(edge[0], edge[1])]
+            } else {
+                vec![(edge[0], edge[1]), (edge[1], edge[0])]
+            })
+            .collect();
+
+        let (graph_type, edge_type) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+
+        let mut dot = format!("{graph_type} {{\n");
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let mut attributes = vec![format!("label=\"{}\"", escape(&node.val.to_string()))];
+
+            if highlight_nodes.contains(&idx) {
+                attributes.push("style=filled".to_string());
+                attributes.push("fillcolor=lightblue".to_string());
+            }
+
+            if path_nodes.contains(&idx) {
+                attributes.push("color=red".to_string());
+                attributes.push("penwidth=2".to_string());
+            }
+
+            dot += &format!("    {idx} [{}];\n", attributes.join(", "));
+        }
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            // The edge back is stored in the other node
+            for c in node.children.iter().filter(|c| self.directed || idx <= c.idx) {
+                let mut attributes = vec![];
+
+                if self.weighted {
+                    attributes.push(format!("label=\"{}\"", c.weight));
+                }
+
+                if path_edges.contains(&(idx, c.idx)) {
+                    attributes.push("color=red".to_string());
+                    attributes.push("penwidth=2".to_string());
+                }
+
+                if attributes.is_empty() {
+                    dot += &format!("    {idx} {edge_type} {};\n", c.idx);
+                } else {
+                    dot += &format!("    {idx} {edge_type} {} [{}];\n", c.idx, attributes.join(", "));
+                }
+            }
+        }
+
+        dot += "}\n";
+
+        dot
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,407 @@
+use std::io;
+use std::io::{BufRead, Write};
+use crate::array::graph::{Child, Graph};
+use crate::array::graph::weight::Weight;
+use crate::utils::read_file::{GraphParseError, GraphParseErrorKind, LineParser};
+
+/*
+Other graph formats
+
+The formats only describe edges, so the value of every node read is its index.
+ */
+
+// Graph with num_nodes nodes where the value is the index. The count comes from the file, so it is only trusted as far
+// as the file goes: a file can not describe more nodes than it has characters, and a short file with a huge count
+// does not get to allocate all of them
+fn index_graph<W>(num_nodes: usize, input_len: usize) -> Result<Graph<usize, W>, GraphParseErrorKind> {
+    if num_nodes > input_len {
+        return Err(GraphParseErrorKind::InvalidHeader(
+            format!("{num_nodes} nodes is more than a file of {input_len} characters can describe")
+        ));
+    }
+
+    let mut graph = Graph::<usize, W>::try_new_with_size(num_nodes)
+        .map_err(|_| GraphParseErrorKind::InvalidHeader(format!("there is not enough memory for {num_nodes} nodes")))?;
+
+    graph.nodes.iter_mut().enumerate().for_each(|(idx, node)| node.val = idx);
+
+    Ok(graph)
+}
+
+fn lines(reader: impl BufRead) -> impl Iterator<Item = Result<(usize, String), GraphParseError>> {
+    reader.lines()
+        .enumerate()
+        .map(|(i, line)| line
+            .map(|line| (i + 1, line))
+            .map_err(|err| GraphParseError::io(i + 1, err)))
+}
+
+/*
+Edge list
+
+One edge per line, "from to" or "from to weight", indexes start at 0.
+Empty lines and lines starting with # are skipped. The number of nodes is the highest index + 1.
+ */
+pub(crate) fn read_edge_list<W: Weight>(reader: impl BufRead) -> Result<Graph<usize, W>, GraphParseError> {
+    let mut edges: Vec<(usize, usize, Option<W>)> = vec![];
+    let mut weighted: Option<bool> = None;
+
+    // (index, line, column) of the highest index, the graph needs one node more than that
+    let mut highest: Option<(usize, usize, usize)> = None;
+    let mut input_len = 0;
+
+    for line in lines(reader) {
+        let (line_number, line) = line?;
+        let mut parser = LineParser::new(line_number, &line);
+
+        input_len += line.len() + 1;
+
+        if parser.peek().is_none_or(|token| token.starts_with('#')) {
+            continue;
+        }
+
+        let (from_column, from): (usize, usize) = parser.next("the from index")?;
+        let (to_column, to): (usize, usize) = parser.next("the to index")?;
+
+        for (idx, column) in [(from, from_column), (to, to_column)] {
+            if highest.is_none_or(|(highest_idx, _, _)| idx > highest_idx) {
+                highest = Some((idx, line_number, column));
+            }
+        }
+
+        let weight = if parser.remaining() > 0 {
+            Some(parser.next::<W>("a weight")?.1)
+        } else {
+            None
+        };
+
+        if parser.remaining() > 0 {
+            let (column, token): (usize, String) = parser.next("the end of the line")?;
+
+            return Err(parser.error(Some(column), GraphParseErrorKind::InvalidToken { expected: "the end of the line", token }));
+        }
+
+        if *weighted.get_or_insert(weight.is_some()) != weight.is_some() {
+            return Err(parser.error(None, GraphParseErrorKind::MixedWeights));
+        }
+
+        edges.push((from, to, weight));
+    }
+
+    let mut graph = match highest {
+        None => index_graph(0, input_len).ok(),
+        Some((idx, _, _)) => idx.checked_add(1).and_then(|num_nodes| index_graph(num_nodes, input_len).ok())
+    }.ok_or_else(|| {
+        let (index, line, column) = highest.unwrap();
+
+        // The rest of the edges fit in a graph with this many nodes
+        let num_nodes = edges.iter()
+            .flat_map(|(from, to, _)| [*from, *to])
+            .filter(|idx| *idx < index)
+            .max()
+            .map_or(0, |idx| idx + 1);
+
+        GraphParseError {
+            line,
+            column: Some(column),
+            cause: GraphParseErrorKind::IndexOutOfRange { index, num_nodes }
+        }
+    })?;
+
+    graph.weighted = weighted.unwrap_or(false);
+
+    for (from, to, weight) in edges {
+        graph.nodes[from].add_child(Child::new_with_weight(to, weight.unwrap_or(W::zero())));
+    }
+
+    Ok(graph)
+}
+
+/*
+DIMACS shortest path (.gr)
+
+c comment
+p sp <nodes> <arcs>
+a <from> <to> <weight>
+
+Indexes start at 1.
+ */
+pub(crate) fn read_dimacs<W: Weight>(reader: impl BufRead) -> Result<Graph<usize, W>, GraphParseError> {
+    // (number of nodes, line, column of the number of nodes)
+    let mut size: Option<(usize, usize, usize)> = None;
+    let mut arcs: Vec<(usize, usize, W)> = vec![];
+    let mut expected_arcs = 0;
+    let mut last_line = 0;
+    let mut input_len = 0;
+
+    for line in lines(reader) {
+        let (line_number, line) = line?;
+        let mut parser = LineParser::new(line_number, &line);
+
+        last_line = line_number;
+        input_len += line.len() + 1;
+
+        match parser.peek() {
+            None | Some("c") => continue,
+            Some("p") => {
+                if size.is_some() {
+                    return Err(parser.error(Some(1), GraphParseErrorKind::InvalidHeader("more than one problem line".to_string())));
+                }
+
+                let _: (usize, String) = parser.next("p")?;
+                let (column, problem): (usize, String) = parser.next("the problem type")?;
+
+                if problem != "sp" {
+                    return Err(parser.error(Some(column), GraphParseErrorKind::InvalidHeader(format!("expected problem type sp, found {problem}"))));
+                }
+
+                let (column, num_nodes): (usize, usize) = parser.next("the number of nodes")?;
+                expected_arcs = parser.next::<usize>("the number of arcs")?.1;
+
+                size = Some((num_nodes, line_number, column));
+            }
+            Some("a") => {
+                let Some((num_nodes, _, _)) = size else {
+                    return Err(parser.error(Some(1), GraphParseErrorKind::InvalidHeader("arc before the problem line".to_string())));
+                };
+
+                let _: (usize, String) = parser.next("a")?;
+                let from = one_based_index(&mut parser, num_nodes, "the from index")?;
+                let to = one_based_index(&mut parser, num_nodes, "the to index")?;
+                let (_, weight): (usize, W) = parser.next("a weight")?;
+
+                arcs.push((from, to, weight));
+            }
+            Some(token) => {
+                return Err(parser.error(Some(1), GraphParseErrorKind::InvalidToken {
+                    expected: "a line starting with c, p or a",
+                    token: token.to_string()
+                }));
+            }
+        }
+    }
+
+    let (num_nodes, line, column) = size.ok_or(GraphParseError {
+        line: last_line,
+        column: None,
+        cause: GraphParseErrorKind::InvalidHeader("no problem line (p sp <nodes> <arcs>)".to_string())
+    })?;
+
+    if arcs.len() != expected_arcs {
+        return Err(GraphParseError {
+            line: last_line,
+            column: None,
+            cause: GraphParseErrorKind::WrongEdgeCount { expected: expected_arcs, found: arcs.len() }
+        });
+    }
+
+    let mut graph = index_graph(num_nodes, input_len)
+        .map_err(|cause| GraphParseError { line, column: Some(column), cause })?;
+    graph.weighted = true;
+
+    for (from, to, weight) in arcs {
+        graph.nodes[from].add_child(Child::new_with_weight(to, weight));
+    }
+
+    Ok(graph)
+}
+
+// Index starting at 1 in the file, starting at 0 in the graph
+fn one_based_index(parser: &mut LineParser, num_nodes: usize, expected: &'static str) -> Result<usize, GraphParseError> {
+    let (column, idx): (usize, usize) = parser.next(expected)?;
+
+    if idx == 0 || idx > num_nodes {
+        return Err(parser.error(Some(column), GraphParseErrorKind::IndexOutOfRange { index: idx, num_nodes }));
+    }
+
+    Ok(idx - 1)
+}
+
+/*
+Matrix Market
+
+%%MatrixMarket matrix coordinate <integer|real|pattern> <general|symmetric>
+% comment
+<rows> <columns> <entries>
+<row> <column> [value]
+
+Indexes start at 1. An entry at row i and column j is an edge from i to j.
+Symmetric matrices are read as undirected graphs, pattern matrices as unweighted graphs.
+Real values have to be whole numbers if the weights are integers.
+ */
+pub(crate) fn read_matrix_market<W: Weight>(reader: impl BufRead) -> Result<Graph<usize, W>, GraphParseError> {
+    let mut lines = lines(reader);
+
+    let (_, header) = lines.next().unwrap_or(Ok((1, String::new())))?;
+    let mut input_len = header.len() + 1;
+    let header_error = |reason: &str| GraphParseError {
+        line: 1,
+        column: None,
+        cause: GraphParseErrorKind::InvalidHeader(reason.to_string())
+    };
+
+    let header: Vec<String> = header.split_whitespace().map(|token| token.to_lowercase()).collect();
+
+    if header.len() != 5 || header[0] != "%%matrixmarket" || header[1] != "matrix" || header[2] != "coordinate" {
+        return Err(header_error("expected %%MatrixMarket matrix coordinate <field> <symmetry>"));
+    }
+
+    let weighted = match header[3].as_str() {
+        "integer" | "real" => true,
+        "pattern" => false,
+        _ => return Err(header_error("the field has to be integer, real or pattern"))
+    };
+
+    let real = header[3] == "real";
+
+    let directed = match header[4].as_str() {
+        "general" => true,
+        "symmetric" => false,
+        _ => return Err(header_error("the symmetry has to be general or symmetric"))
+    };
+
+    // (number of nodes, line, column of the number of rows)
+    let mut size: Option<(usize, usize, usize)> = None;
+    let mut entries: Vec<(usize, usize, W)> = vec![];
+    let mut expected_entries = 0;
+    let mut last_line = 1;
+
+    for line in lines {
+        let (line_number, line) = line?;
+        let mut parser = LineParser::new(line_number, &line);
+
+        last_line = line_number;
+        input_len += line.len() + 1;
+
+        if parser.peek().is_none_or(|token| token.starts_with('%')) {
+            continue;
+        }
+
+        let Some((num_nodes, _, _)) = size else {
+            let (rows_column, rows): (usize, usize) = parser.next("the number of rows")?;
+            let (column, columns): (usize, usize) = parser.next("the number of columns")?;
+            expected_entries = parser.next::<usize>("the number of entries")?.1;
+
+            if rows != columns {
+                return Err(parser.error(Some(column), GraphParseErrorKind::InvalidHeader(format!("the matrix has to be square, found {rows} x {columns}"))));
+            }
+
+            size = Some((rows, line_number, rows_column));
+
+            continue;
+        };
+
+        let from = one_based_index(&mut parser, num_nodes, "the row")?;
+        let to = one_based_index(&mut parser, num_nodes, "the column")?;
+
+        let weight = match (weighted, real, W::INTEGER) {
+            (false, _, _) => W::zero(),
+            (true, false, _) => parser.next::<W>("an integer value")?.1,
+            (true, true, false) => parser.next::<W>("a real value")?.1,
+            (true, true, true) => {
+                let (column, value): (usize, f64) = parser.next("a real value")?;
+
+                // Whole numbers are written without decimals, so they parse as the integer type if they fit
+                value.to_string().parse::<W>()
+                    .ok()
+                    .filter(|_| value.fract() == 0.0)
+                    .ok_or_else(|| parser.error(Some(column), GraphParseErrorKind::InvalidToken {
+                        expected: "a whole number",
+                        token: value.to_string()
+                    }))?
+            }
+        };
+
+        entries.push((from, to, weight));
+    }
+
+    let (num_nodes, line, column) = size.ok_or(GraphParseError {
+        line: last_line,
+        column: None,
+        cause: GraphParseErrorKind::InvalidHeader("no size line (<rows> <columns> <entries>)".to_string())
+    })?;
+
+    if entries.len() != expected_entries {
+        return Err(GraphParseError {
+            line: last_line,
+            column: None,
+            cause: GraphParseErrorKind::WrongEdgeCount { expected: expected_entries, found: entries.len() }
+        });
+    }
+
+    let mut graph = index_graph(num_nodes, input_len)
+        .map_err(|cause| GraphParseError { line, column: Some(column), cause })?;
+    graph.weighted = weighted;
+    graph.directed = directed;
+
+    for (from, to, weight) in entries {
+        graph.add_edge_with_weight(from, to, weight);
+    }
+
+    Ok(graph)
+}
+
+/*
+Writing
+ */
+impl<T, W, E> Graph<T, W, E>
+    where W: Weight {
+    pub(crate) fn write_edge_list(&self, mut writer: impl Write) -> io::Result<()> {
+        for (idx, c) in self.edges() {
+            if self.weighted {
+                writeln!(writer, "{idx} {} {}", c.idx, c.weight)?;
+            } else {
+                writeln!(writer, "{idx} {}", c.idx)?;
+            }
+        }
+
+        writer.flush()
+    }
+
+    // Unweighted graphs are written with weight 1 on every arc
+    pub(crate) fn write_dimacs(&self, mut writer: impl Write) -> io::Result<()> {
+        let edges: Vec<(usize, &Child<W, E>)> = self.edges().collect();
+
+        writeln!(writer, "p sp {} {}", self.nodes.len(), edges.len())?;
+
+        for (idx, c) in edges {
+            writeln!(writer, "a {} {} {}", idx + 1, c.idx + 1, self.edge_cost(c))?;
+        }
+
+        writer.flush()
+    }
+
+    // Undirected graphs are written as symmetric matrices with only the lower triangle
+    pub(crate) fn write_matrix_market(&self, mut writer: impl Write) -> io::Result<()> {
+        let edges: Vec<(usize, &Child<W, E>)> = self.edges()
+            .filter(|(idx, c)| self.directed || *idx >= c.idx)
+            .collect();
+
+        let field = match (self.weighted, W::INTEGER) {
+            (false, _) => "pattern",
+            (true, true) => "integer",
+            (true, false) => "real"
+        };
+        let symmetry = if self.directed { "general" } else { "symmetric" };
+
+        writeln!(writer, "%%MatrixMarket matrix coordinate {field} {symmetry}")?;
+        writeln!(writer, "{} {} {}", self.nodes.len(), self.nodes.len(), edges.len())?;
+
+        for (idx, c) in edges {
+            if self.weighted {
+                writeln!(writer, "{} {} {}", idx + 1, c.idx + 1, c.weight)?;
+            } else {
+                writeln!(writer, "{} {}", idx + 1, c.idx + 1)?;
+            }
+        }
+
+        writer.flush()
+    }
+
+    // Every edge in the graph as (from, child)
+    fn edges(&self) -> impl Iterator<Item = (usize, &Child<W, E>)> {
+        self.nodes.iter()
+            .enumerate()
+            .flat_map(|(idx, node)| node.children.iter().map(move |c| (idx, c)))
+    }
+}
@@ -1,154 +1,337 @@
-use std::fmt::Debug;
+use std::error::Error;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use crate::array::graph::{Child, Graph};
+use std::str::FromStr;
+use crate::array::graph::{Child, Graph, Node};
+use crate::array::graph::label::NoLabel;
+use crate::array::graph::weight::Weight;
 
-pub(crate) trait FileReader<T>
-    where T: Debug + Ord {
-    fn read_file(file_path: &str, weighted: bool) -> Graph<T>;
+pub(crate) trait FileReader<T, W = i64, E = NoLabel>
+    where T: FromStr + Default, W: Weight, E: FromStr {
+    fn read_file(file_path: &str, weighted: bool) -> Graph<T, W, E>;
+
+    fn try_read_file(file_path: &str, weighted: bool) -> Result<Graph<T, W, E>, GraphParseError>;
+
+    // Weights are found by looking at the number of values after the child count on every line
+    fn try_read_file_inferred(file_path: &str) -> Result<Graph<T, W, E>, GraphParseError>;
+
+    // The file has to list every edge in both directions
+    fn read_file_undirected(file_path: &str, weighted: bool) -> Graph<T, W, E>
+        where E: PartialEq {
+        Self::try_read_file_undirected(file_path, weighted)
+            .unwrap_or_else(|err| panic!("Could not read the graph in ({file_path}): {err}"))
+    }
+
+    fn try_read_file_undirected(file_path: &str, weighted: bool) -> Result<Graph<T, W, E>, GraphParseError>
+        where E: PartialEq {
+        let mut graph = Self::try_read_file(file_path, weighted)?;
+
+        graph.directed = false;
+
+        graph.validate_undirected().map_err(|edges| GraphParseError {
+            line: 0,
+            column: None,
+            cause: GraphParseErrorKind::AsymmetricEdges(edges)
+        })?;
+
+        Ok(graph)
+    }
 }
 
-impl FileReader<char> for Graph<char> {
-    fn read_file(file_path: &str, weighted: bool) -> Graph<char> {
-        // Read the file
-        // Refactor to send a result and not just Graph?
-        let file = File::open(file_path)
-            .expect(&*format!("Did not fine the file at the given file path: ({file_path})"));
-
-        let mut lines = BufReader::new(file)
-            .lines();
-
-        // Get the number of nodes described in the rest of the file, the first line
-        let num_nodes: usize = lines
-            .next()
-            .expect("Could not read the first line")
-            .expect("Error while fetching line")
-            .parse()
-            .expect("could not parse string to int");
-
-        // Create the graph and fill with the correct amount of elements
-        let mut graph = Graph::<char>::new_with_size(num_nodes);
-
-        graph.weighted = weighted;
-
-        // For the rest of the lines in the file add the node to graph
-        let multiply = if weighted {
-            graph.weighted = true;
-            2
-        } else {
-            1
-        };
+impl<T, W, E> FileReader<T, W, E> for Graph<T, W, E>
+    where T: FromStr + Default, W: Weight, E: FromStr {
+    fn read_file(file_path: &str, weighted: bool) -> Graph<T, W, E> {
+        Graph::try_read_file(file_path, weighted)
+            .unwrap_or_else(|err| panic!("Could not read the graph in ({file_path}): {err}"))
+    }
+
+    fn try_read_file(file_path: &str, weighted: bool) -> Result<Graph<T, W, E>, GraphParseError> {
+        read_graph(open_file(file_path)?, Some(weighted))
+    }
+
+    fn try_read_file_inferred(file_path: &str) -> Result<Graph<T, W, E>, GraphParseError> {
+        read_graph(open_file(file_path)?, None)
+    }
+}
+
+fn open_file(file_path: &str) -> Result<BufReader<File>, GraphParseError> {
+    File::open(file_path)
+        .map(BufReader::new)
+        .map_err(|err| GraphParseError::io(0, err))
+}
+
+/*
+Fallible reading
+ */
+#[derive(Debug)]
+pub struct GraphParseError {
+    pub(crate) line: usize,             // Line number in the file, starting at 1. 0 if the file could not be read
+    pub(crate) column: Option<usize>,   // Column of the token that failed, starting at 1
+    pub(crate) cause: GraphParseErrorKind
+}
+
+#[derive(Debug)]
+pub enum GraphParseErrorKind {
+    Io(std::io::Error),
+    MissingToken(&'static str),
+    InvalidToken { expected: &'static str, token: String },
+    IndexOutOfRange { index: usize, num_nodes: usize },
+    WrongChildCount { expected: usize, found: usize },  // Number of tokens after the child count
+    DanglingChild { child: usize, num_nodes: usize },
+    InvalidHeader(String),
+    WrongEdgeCount { expected: usize, found: usize },
+    MixedWeights,   // Some edges have a weight and some do not
+    AsymmetricEdges(Vec<(usize, usize)>)    // Edges in an undirected graph without a matching edge back
+}
+
+impl GraphParseError {
+    pub(crate) fn io(line: usize, err: std::io::Error) -> GraphParseError {
+        GraphParseError {
+            line,
+            column: None,
+            cause: GraphParseErrorKind::Io(err)
+        }
+    }
+}
+
+impl Display for GraphParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {column}: ", self.line)?,
+            None => write!(f, "line {}: ", self.line)?
+        }
+
+        match &self.cause {
+            GraphParseErrorKind::Io(err) => write!(f, "could not read the file ({err})"),
+            GraphParseErrorKind::MissingToken(expected) => write!(f, "missing {expected}"),
+            GraphParseErrorKind::InvalidToken { expected, token } => write!(f, "could not parse ({token}) as {expected}"),
+            GraphParseErrorKind::IndexOutOfRange { index, num_nodes } =>
+                write!(f, "node index {index} is out of range, the graph has {num_nodes} nodes"),
+            GraphParseErrorKind::WrongChildCount { expected, found } =>
+                write!(f, "expected {expected} values after the child count, found {found}"),
+            GraphParseErrorKind::DanglingChild { child, num_nodes } =>
+                write!(f, "child {child} does not exist, the graph has {num_nodes} nodes"),
+            GraphParseErrorKind::InvalidHeader(reason) => write!(f, "invalid header, {reason}"),
+            GraphParseErrorKind::WrongEdgeCount { expected, found } =>
+                write!(f, "the header says {expected} edges, found {found}"),
+            GraphParseErrorKind::MixedWeights => write!(f, "some edges have a weight and some do not"),
+            GraphParseErrorKind::AsymmetricEdges(edges) =>
+                write!(f, "the graph is not undirected, edges without a matching edge back: {edges:?}")
+        }
+    }
+}
 
-        // For the rest of the lines in the file add the node to graph
-        for line in lines {
-            // split the line to a vector
-            let split_line: Vec<String> = line
-                .expect("Did not find line")
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string())
-                .collect();
-
-            // Get index of node to edit
-            let current_node_index = split_line[0]
-                .parse::<usize>()
-                .expect("Cant parse the node index. First value of line");
-
-            // Set the correct value of the current node
-            graph.nodes[current_node_index].val = split_line[1]
-                .parse()
-                .expect("Cant parse node value. Second value of line");
-
-            let childs = split_line[2]
-                .parse::<usize>()
-                .expect("Cant parse number of child nodes. Third value of line");
-
-            // Add childs to correct node in the graph
-            for i in 0..childs {
-                let weight = if weighted {
-                    split_line[3+(i * multiply)+1].parse::<u32>().unwrap()
-                } else {
-                    0
-                };
-
-                graph.nodes[current_node_index]
-                    .add_child(Child::new_with_weight(split_line[3+(i * multiply)]
-                                              .parse::<usize>()
-                                              .expect(&*format!("Could not parse ({}) to usize", split_line[3+(i * multiply)])), weight));
+impl Error for GraphParseError {}
+
+// Whitespace separated tokens on a line, with the column they start at
+fn tokenize(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut start: Option<usize> = None;
+
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                tokens.push((s + 1, &line[s..i]));
+                start = None;
             }
+            _ => {}
         }
+    }
 
-        graph
+    if let Some(s) = start {
+        tokens.push((s + 1, &line[s..]));
     }
+
+    tokens
 }
 
-impl FileReader<String> for Graph<String> {
-    fn read_file(file_path: &str, weighted: bool) -> Graph<String> {
-        // Read the file
-        // Refactor to send a result and not just Graph?
-        let file = File::open(file_path)
-            .expect(&*format!("Did not fine the file at the given file path: ({file_path})"));
-
-        let mut lines = BufReader::new(file)
-            .lines();
-
-        // Get the number of nodes described in the rest of the file, the first line
-        let num_nodes: usize = lines
-            .next()
-            .expect("Could not read the first line")
-            .expect("Error while fetching line")
-            .parse()
-            .expect("could not parse string to int");
-
-        // Create the graph and fill with the correct amount of elements
-        let mut graph = Graph::<String>::new_with_size(num_nodes);
-
-        let multiply = if weighted {
-            graph.weighted = true;
-            2
-        } else {
-            1
+pub(crate) struct LineParser<'a> {
+    line: usize,
+    tokens: std::vec::IntoIter<(usize, &'a str)>
+}
+
+impl<'a> LineParser<'a> {
+    pub(crate) fn new(line: usize, text: &'a str) -> LineParser<'a> {
+        LineParser {
+            line,
+            tokens: tokenize(text).into_iter()
+        }
+    }
+
+    pub(crate) fn error(&self, column: Option<usize>, cause: GraphParseErrorKind) -> GraphParseError {
+        GraphParseError {
+            line: self.line,
+            column,
+            cause
+        }
+    }
+
+    pub(crate) fn next<V: FromStr>(&mut self, expected: &'static str) -> Result<(usize, V), GraphParseError> {
+        let (column, token) = self.tokens.next()
+            .ok_or_else(|| self.error(None, GraphParseErrorKind::MissingToken(expected)))?;
+
+        token.parse()
+            .map(|value| (column, value))
+            .map_err(|_| self.error(Some(column), GraphParseErrorKind::InvalidToken {
+                expected,
+                token: token.to_string()
+            }))
+    }
+
+    // A value that can have a label after it, "3" or "3:road"
+    pub(crate) fn next_labeled<V: FromStr, E: FromStr>(&mut self, expected: &'static str) -> Result<(usize, V, Option<E>), GraphParseError> {
+        let (column, token) = self.tokens.next()
+            .ok_or_else(|| self.error(None, GraphParseErrorKind::MissingToken(expected)))?;
+
+        let (value, label) = match token.split_once(':') {
+            Some((value, label)) => (value, Some(label)),
+            None => (token, None)
+        };
+
+        let value = value.parse()
+            .map_err(|_| self.error(Some(column), GraphParseErrorKind::InvalidToken {
+                expected,
+                token: value.to_string()
+            }))?;
+
+        let label = match label {
+            Some(label) => Some(label.parse().map_err(|_| self.error(
+                Some(column + token.len() - label.len()),
+                GraphParseErrorKind::InvalidToken { expected: "a label", token: label.to_string() }
+            ))?),
+            None => None
         };
 
-        // For the rest of the lines in the file add the node to graph
-        for line in lines {
-            // split the line to a vector
-            let split_line: Vec<String> = line
-                .expect("Did not find line")
-                .split(' ')
-                .filter(|s| !s.is_empty())
-                .map(|s| s.to_string())
-                .collect();
-
-            // Get index of node to edit
-            let current_node_index = split_line[0]
-                .parse::<usize>()
-                .expect("Cant parse the node index. First value of line");
-
-            // Set the correct value of the current node
-            graph.nodes[current_node_index].val = split_line[1]
-                .parse()
-                .expect("Cant parse node value. Second value of line");
-
-            let childs = split_line[2]
-                .parse::<usize>()
-                .expect("Cant parse number of child nodes. Third value of line");
-
-            // Add childs to correct node in the graph
-            for i in 0..childs {
-                let weight = if weighted {
-                    split_line[3+(i * multiply)+1].parse::<u32>().unwrap()
-                } else {
-                    0
-                };
-
-                graph.nodes[current_node_index]
-                    .add_child(Child::new_with_weight(split_line[3+(i * multiply)]
-                                              .parse::<usize>()
-                                              .expect(&*format!("Could not parse ({}) to usize", split_line[3+(i * multiply)])), weight));
+        Ok((column, value, label))
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.tokens.len()
+    }
+
+    pub(crate) fn peek(&self) -> Option<&'a str> {
+        self.tokens.as_slice().first().map(|(_, token)| *token)
+    }
+}
+
+// Reads the graph format used in src/files. If weighted is None it is decided for every line
+// by the number of values after the child count.
+// The nodes are only made after the whole file is read, and every node needs a line, so a header with a huge count
+// can not make it allocate more than the file describes.
+pub(crate) fn read_graph<T, W, E, R>(reader: R, weighted: Option<bool>) -> Result<Graph<T, W, E>, GraphParseError>
+    where T: FromStr + Default, W: Weight, E: FromStr, R: BufRead {
+    let mut lines = reader.lines().enumerate();
+
+    // Get the number of nodes described in the rest of the file, the first line
+    let first_line = match lines.next() {
+        Some((_, line)) => line.map_err(|err| GraphParseError::io(1, err))?,
+        None => String::new()
+    };
+
+    let mut parser = LineParser::new(1, &first_line);
+    let (header_column, num_nodes): (usize, usize) = parser.next("the number of nodes")?;
+
+    let mut read_nodes: Vec<(usize, Node<T, W, E>)> = vec![];
+    let mut any_weighted = weighted.unwrap_or(false);
+
+    for (i, line) in lines {
+        let line = line.map_err(|err| GraphParseError::io(i + 1, err))?;
+        let mut parser = LineParser::new(i + 1, &line);
+
+        if parser.remaining() == 0 {
+            continue;
+        }
+
+        let (column, idx): (usize, usize) = parser.next("a node index")?;
+
+        if idx >= num_nodes {
+            return Err(parser.error(Some(column), GraphParseErrorKind::IndexOutOfRange { index: idx, num_nodes }));
+        }
+
+        let (_, val): (usize, T) = parser.next("a node value")?;
+        let (column, num_children): (usize, usize) = parser.next("the number of children")?;
+
+        // Every child needs at least one value on the line, so a larger count is wrong before anything is multiplied
+        if num_children > parser.remaining() {
+            return Err(parser.error(None, GraphParseErrorKind::WrongChildCount {
+                expected: num_children,
+                found: parser.remaining()
+            }));
+        }
+
+        let weighted_count = num_children.checked_mul(2);
+
+        let line_weighted = match weighted {
+            Some(w) => w,
+            None => parser.remaining() != num_children && weighted_count == Some(parser.remaining())
+        };
+
+        let expected = match (line_weighted, weighted_count) {
+            (false, _) => num_children,
+            (true, Some(count)) => count,
+            (true, None) => return Err(parser.error(Some(column), GraphParseErrorKind::InvalidToken {
+                expected: "the number of children",
+                token: num_children.to_string()
+            }))
+        };
+
+        if parser.remaining() != expected {
+            return Err(parser.error(None, GraphParseErrorKind::WrongChildCount {
+                expected,
+                found: parser.remaining()
+            }));
+        }
+
+        any_weighted |= line_weighted;
+        let mut node = Node::new(val);
+
+        for _ in 0..num_children {
+            let (column, child, label): (usize, usize, Option<E>) = parser.next_labeled("a child index")?;
+
+            if child >= num_nodes {
+                return Err(parser.error(Some(column), GraphParseErrorKind::DanglingChild { child, num_nodes }));
             }
+
+            let weight = if line_weighted {
+                parser.next("a weight")?.1
+            } else {
+                W::zero()
+            };
+
+            node.add_child(Child { idx: child, weight, label });
         }
 
-        graph
+        read_nodes.push((idx, node));
     }
-}
\ No newline at end of file
+
+    // Stable, so a node with more than one line keeps the children in the order of the lines
+    read_nodes.sort_by_key(|(idx, _)| *idx);
+
+    let mut graph: Graph<T, W, E> = Graph::new();
+    graph.weighted = any_weighted;
+
+    for (idx, node) in read_nodes {
+        let len = graph.nodes.len();
+
+        if idx == len {
+            graph.nodes.push(node);
+        } else if idx + 1 == len {
+            // Another line for the last node, the value is replaced and the children are added
+            graph.nodes[idx].val = node.val;
+            graph.nodes[idx].children.extend(node.children);
+        } else {
+            break;
+        }
+    }
+
+    if graph.nodes.len() != num_nodes {
+        return Err(parser.error(Some(header_column), GraphParseErrorKind::InvalidHeader(
+            format!("the header says {num_nodes} nodes, but there is no line for node {}", graph.nodes.len())
+        )));
+    }
+
+    Ok(graph)
+}
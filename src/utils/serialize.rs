@@ -0,0 +1,156 @@
+use serde::Deserialize;
+use crate::array::avl_tree::AVLTree;
+use crate::array::binary_tree::BinaryTree;
+use crate::array::graph::{Graph, Node};
+use crate::array::graph::node_id::next_graph_tag;
+use crate::array::graph::weight::Weight;
+
+/*
+Serde support
+
+The structures are deserialized in to these first, and only turned in to the real structure if they are valid.
+ */
+#[derive(Deserialize)]
+pub struct GraphData<T, W, E> {
+    nodes: Vec<Node<T, W, E>>,
+    weighted: bool,
+    directed: bool
+}
+
+impl<T, W, E> TryFrom<GraphData<T, W, E>> for Graph<T, W, E>
+    where W: Weight, E: PartialEq {
+    type Error = String;
+
+    fn try_from(value: GraphData<T, W, E>) -> Result<Self, Self::Error> {
+        let num_nodes = value.nodes.len();
+
+        for (idx, node) in value.nodes.iter().enumerate() {
+            if let Some(child) = node.children.iter().find(|c| c.idx >= num_nodes) {
+                return Err(format!("Node {idx} has the child {}, but there are only {num_nodes} nodes", child.idx));
+            }
+        }
+
+        let graph = Graph {
+            nodes: value.nodes,
+            weighted: value.weighted,
+            directed: value.directed,
+            tag: next_graph_tag(),
+            generation: 0
+        };
+
+        if !graph.directed {
+            if let Err(edges) = graph.validate_undirected() {
+                return Err(format!("The graph is undirected, but these edges have no edge back: {edges:?}"));
+            }
+        }
+
+        Ok(graph)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct BinaryTreeData<T> {
+    root: Vec<Option<T>>,
+    nodes: u32,
+    height: u32
+}
+
+impl<T> TryFrom<BinaryTreeData<T>> for BinaryTree<T>
+    where T: PartialOrd {
+    type Error = String;
+
+    fn try_from(value: BinaryTreeData<T>) -> Result<Self, Self::Error> {
+        check_tree_array(&value.root, value.nodes, value.height)?;
+
+        Ok(BinaryTree {
+            root: value.root,
+            nodes: value.nodes,
+            height: value.height
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct AVLTreeData<T> {
+    root: Vec<Option<T>>,
+    balance_factor: Vec<u32>,
+    nodes: u32,
+    height: u32
+}
+
+impl<T> TryFrom<AVLTreeData<T>> for AVLTree<T>
+    where T: PartialOrd {
+    type Error = String;
+
+    fn try_from(value: AVLTreeData<T>) -> Result<Self, Self::Error> {
+        check_tree_array(&value.root, value.nodes, value.height)?;
+
+        if value.balance_factor.len() != value.root.len() {
+            return Err(format!(
+                "There are {} balance factors, but the array has {} elements",
+                value.balance_factor.len(), value.root.len()
+            ));
+        }
+
+        if let Some(idx) = (0..value.root.len()).find(|idx| value.root[*idx].is_none() && value.balance_factor[*idx] != 0) {
+            return Err(format!("Element {idx} is empty, but has the balance factor {}", value.balance_factor[idx]));
+        }
+
+        Ok(AVLTree {
+            root: value.root,
+            balance_factor: value.balance_factor,
+            nodes: value.nodes,
+            height: value.height
+        })
+    }
+}
+
+// The array has to be full levels, 2^levels - 1 elements, and every value has to be in the right place
+fn check_tree_array<T>(root: &[Option<T>], nodes: u32, height: u32) -> Result<(), String>
+    where T: PartialOrd {
+    if !(root.len() + 1).is_power_of_two() {
+        return Err(format!("The array has {} elements, it has to be 2^levels - 1", root.len()));
+    }
+
+    // nodes is only set when the tree gets a new level
+    if nodes as usize != root.len() && !(nodes == 0 && root.len() <= 1) {
+        return Err(format!("nodes is {nodes}, but the array has {} elements", root.len()));
+    }
+
+    // new_with_data counts the first level as height 1, increase_levels does not
+    let levels = (root.len() + 1).trailing_zeros();
+
+    if height != levels && height + 1 != levels {
+        return Err(format!("The height is {height}, but the array has {levels} levels"));
+    }
+
+    for (idx, val) in root.iter().enumerate() {
+        let val = match val {
+            Some(val) => val,
+            None => continue
+        };
+
+        // Check the value against every node on the way up to the root
+        let mut child = idx;
+
+        while child != 0 {
+            let parent = (child - 1) / 2;
+
+            let parent_val = match &root[parent] {
+                Some(parent_val) => parent_val,
+                None => return Err(format!("Element {idx} has a value, but element {parent} above it is empty"))
+            };
+
+            // Smaller values to the left, bigger to the right
+            let in_order = if child % 2 == 1 { val < parent_val } else { val > parent_val };
+
+            if !in_order {
+                return Err(format!("Element {idx} is on the wrong side of element {parent}"));
+            }
+
+            child = parent;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+/*
+UnionFind
+
+Disjoint sets of the indexes 0..len, every index starts as its own set. Finding a set points every index on the way
+directly at the root, and the lower tree is always put under the higher one, so both operations are close to constant time.
+ */
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    pub(crate) parent: Vec<usize>,
+    pub(crate) rank: Vec<u8>,     // Upper bound on the height of the tree under the index
+    pub(crate) num_sets: usize
+}
+
+impl UnionFind {
+    pub(crate) fn new(size: usize) -> UnionFind {
+        UnionFind {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            num_sets: size
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub(crate) fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    // Adds a new set with only the new index in it
+    pub(crate) fn add(&mut self) -> usize {
+        self.parent.push(self.len());
+        self.rank.push(0);
+        self.num_sets += 1;
+
+        self.len() - 1
+    }
+
+    // The root of the set the index is in
+    pub(crate) fn find(&mut self, idx: usize) -> usize {
+        let mut root = idx;
+
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = idx;
+
+        while current != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    // Returns false if a and b already were in the same set
+    pub(crate) fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        let (low, high) = if self.rank[a] < self.rank[b] { (a, b) } else { (b, a) };
+
+        self.parent[low] = high;
+
+        if self.rank[low] == self.rank[high] {
+            self.rank[high] += 1;
+        }
+
+        self.num_sets -= 1;
+
+        true
+    }
+
+    pub(crate) fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // Set id of every index. The ids are 0..num_sets, numbered by the lowest index in each set
+    pub(crate) fn set_ids(&mut self) -> Vec<usize> {
+        let mut root_id: Vec<Option<usize>> = vec![None; self.len()];
+        let mut next_id = 0;
+
+        (0..self.len())
+            .map(|idx| {
+                let root = self.find(idx);
+
+                *root_id[root].get_or_insert_with(|| {
+                    next_id += 1;
+                    next_id - 1
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,87 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use crate::array::graph::Graph;
+use crate::array::graph::weight::Weight;
+
+/*
+Write the graph in the same format FileReader reads
+
+ 0 A  3    1  5  6
+ 1 B  0
+
+Node index, value, number of children and the children. For weighted graphs every child is followed by its weight.
+Children with a label are written as index:label.
+ */
+impl<T, W, E> Graph<T, W, E>
+    where T: Display, W: Weight, E: Display {
+    pub(crate) fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let values: Vec<String> = self.nodes.iter().map(|node| node.val.to_string()).collect();
+
+        // A value with whitespace in it would be read back as more than one value
+        if let Some(idx) = values.iter().position(|val| val.is_empty() || val.contains(char::is_whitespace)) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("The value of node {idx} ({}) can not be empty or contain whitespace", values[idx])
+            ));
+        }
+
+        let labels = self.nodes.iter()
+            .enumerate()
+            .flat_map(|(idx, node)| node.children.iter().map(move |c| (idx, c)))
+            .filter_map(|(idx, c)| c.label.as_ref().map(|label| (idx, label.to_string())));
+
+        for (idx, label) in labels {
+            if label.is_empty() || label.contains(char::is_whitespace) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("The label ({label}) on an edge from node {idx} can not be empty or contain whitespace")
+                ));
+            }
+        }
+
+        // Indexes and weights share the same width so the columns line up
+        let number_width = self.nodes.iter()
+            .flat_map(|node| node.children.iter())
+            .filter(|_| self.weighted)
+            .map(|child| child.weight.to_string().len())
+            .chain(std::iter::once(self.nodes.len().saturating_sub(1).to_string().len()))
+            .max()
+            .unwrap_or(1);
+
+        let value_width = values.iter().map(|val| val.chars().count()).max().unwrap_or(0);
+
+        let num_child_width = self.nodes.iter()
+            .map(|node| node.children.len().to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        writeln!(writer, "{}", self.nodes.len())?;
+
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let mut line = format!("{idx:>number_width$} {:<value_width$}  {:>num_child_width$}", values[idx], node.children.len());
+
+            let children: Vec<String> = node.children.iter()
+                .map(|child| if self.weighted {
+                    format!("{:>number_width$} {:>number_width$}", child.labeled_idx(), child.weight)
+                } else {
+                    format!("{:>number_width$}", child.labeled_idx())
+                })
+                .collect();
+
+            if !children.is_empty() {
+                line += "   ";
+                line += &children.join(if self.weighted { "  " } else { " " });
+            }
+
+            writeln!(writer, "{}", line.trim_end())?;
+        }
+
+        writer.flush()
+    }
+
+    pub(crate) fn write_file(&self, file_path: &str) -> io::Result<()> {
+        self.write_to(BufWriter::new(File::create(file_path)?))
+    }
+}